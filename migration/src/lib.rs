@@ -2,6 +2,23 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20240922_075048_create_ideas;
 mod m20241002_082310_create_comments;
+mod m20261018_090000_notify_changes;
+mod m20261018_100000_cascade_comments;
+mod m20261018_110000_idea_status;
+mod m20261018_120000_kind_table;
+mod m20261018_130000_tags;
+mod m20261018_140000_votes;
+mod m20261018_150000_idea_priority;
+mod m20261018_160000_assignees;
+mod m20261018_170000_comment_replies;
+mod m20261018_180000_revisions;
+mod m20261018_190000_soft_delete;
+mod m20261018_200000_anonymous;
+mod m20261018_210000_people;
+mod m20261018_220000_roster;
+mod m20261018_230000_meetings;
+mod m20261018_233000_due_dates;
+mod m20261018_235500_attendee_people;
 
 pub struct Migrator;
 
@@ -11,6 +28,23 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20240922_075048_create_ideas::Migration),
             Box::new(m20241002_082310_create_comments::Migration),
+            Box::new(m20261018_090000_notify_changes::Migration),
+            Box::new(m20261018_100000_cascade_comments::Migration),
+            Box::new(m20261018_110000_idea_status::Migration),
+            Box::new(m20261018_120000_kind_table::Migration),
+            Box::new(m20261018_130000_tags::Migration),
+            Box::new(m20261018_140000_votes::Migration),
+            Box::new(m20261018_150000_idea_priority::Migration),
+            Box::new(m20261018_160000_assignees::Migration),
+            Box::new(m20261018_170000_comment_replies::Migration),
+            Box::new(m20261018_180000_revisions::Migration),
+            Box::new(m20261018_190000_soft_delete::Migration),
+            Box::new(m20261018_200000_anonymous::Migration),
+            Box::new(m20261018_210000_people::Migration),
+            Box::new(m20261018_220000_roster::Migration),
+            Box::new(m20261018_230000_meetings::Migration),
+            Box::new(m20261018_233000_due_dates::Migration),
+            Box::new(m20261018_235500_attendee_people::Migration),
         ]
     }
 }
@@ -1,5 +1,5 @@
 use sea_orm::{ActiveEnum, DbBackend, DeriveActiveEnum, EnumIter, Schema};
-use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
 
 #[derive(DeriveMigrationName)]
 pub struct Migration;
@@ -7,10 +7,18 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let schema = Schema::new(DbBackend::Postgres);
-        manager
-            .create_type(schema.create_enum_from_active_enum::<Kind>())
-            .await?;
+        let backend = manager.get_database_backend();
+        // Only postgres has enum types, everything else stores the kind as text
+        let mut kind = ColumnDef::new(Idea::Kind);
+        if backend == DbBackend::Postgres {
+            let schema = Schema::new(backend);
+            manager
+                .create_type(schema.create_enum_from_active_enum::<Kind>())
+                .await?;
+            kind.custom(Kind::name());
+        } else {
+            kind.string();
+        }
         manager
             .create_table(
                 Table::create()
@@ -27,7 +35,7 @@ impl MigrationTrait for Migration {
                     .col(ColumnDef::new(Idea::Description).string().not_null())
                     .col(ColumnDef::new(Idea::Author).string().not_null())
                     .col(ColumnDef::new(Idea::Solved).boolean().not_null())
-                    .col(ColumnDef::new(Idea::Kind).custom(Kind::name()).not_null())
+                    .col(kind.not_null())
                     .col(ColumnDef::new(Idea::Time).timestamp().not_null())
                     .to_owned(),
             )
@@ -37,7 +45,13 @@ impl MigrationTrait for Migration {
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
         manager
             .drop_table(Table::drop().table(Idea::Table).to_owned())
-            .await
+            .await?;
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .drop_type(Type::drop().name(Kind::name()).to_owned())
+                .await?;
+        }
+        Ok(())
     }
 }
 
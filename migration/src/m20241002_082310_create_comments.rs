@@ -42,8 +42,8 @@ impl MigrationTrait for Migration {
     }
 }
 
-#[derive(DeriveIden)]
-enum Comment {
+#[derive(DeriveIden, Clone, Copy)]
+pub enum Comment {
     Table,
     Id,
     Time,
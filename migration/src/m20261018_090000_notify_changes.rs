@@ -0,0 +1,59 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Sends `<table> <operation> <id>` on the `verneanbud` channel whenever a row
+/// changes. The column used as the id is given as the trigger argument
+const NOTIFY_FUNCTION: &str = r"
+CREATE OR REPLACE FUNCTION verneanbud_notify() RETURNS trigger AS $$
+DECLARE
+    changed jsonb;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed := to_jsonb(OLD);
+    ELSE
+        changed := to_jsonb(NEW);
+    END IF;
+    PERFORM pg_notify(
+        'verneanbud',
+        TG_TABLE_NAME || ' ' || TG_OP || ' ' || (changed ->> TG_ARGV[0])
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // LISTEN/NOTIFY only exists in postgres
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        db.execute_unprepared(NOTIFY_FUNCTION).await?;
+        for table in ["idea", "comment"] {
+            db.execute_unprepared(&format!(
+                "CREATE TRIGGER {table}_notify AFTER INSERT OR UPDATE OR DELETE ON {table} \
+                 FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('id')"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        for table in ["idea", "comment"] {
+            db.execute_unprepared(&format!("DROP TRIGGER {table}_notify ON {table}"))
+                .await?;
+        }
+        db.execute_unprepared("DROP FUNCTION verneanbud_notify")
+            .await?;
+        Ok(())
+    }
+}
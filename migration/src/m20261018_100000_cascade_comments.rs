@@ -0,0 +1,133 @@
+use sea_orm::{DbBackend, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use crate::{m20240922_075048_create_ideas::Idea, m20241002_082310_create_comments::Comment};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The name of the foreign key from the comments to their idea
+const FOREIGN_KEY: &str = "fk-comment-idea";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut comments_on = ColumnDef::new(Comment::CommentsOn);
+        comments_on.integer().not_null();
+        replace_foreign_key(manager, comments_on, ForeignKeyAction::Cascade).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut comments_on = ColumnDef::new(Comment::CommentsOn);
+        comments_on.unsigned().not_null();
+        replace_foreign_key(manager, comments_on, ForeignKeyAction::NoAction).await
+    }
+}
+
+/// Changes the type of `comment.comments_on` and what happens to the
+/// comments when their idea is deleted
+async fn replace_foreign_key(
+    manager: &SchemaManager<'_>,
+    mut comments_on: ColumnDef,
+    on_delete: ForeignKeyAction,
+) -> Result<(), DbErr> {
+    let foreign_key = ForeignKey::create()
+        .name(FOREIGN_KEY)
+        .from(Comment::Table, Comment::CommentsOn)
+        .to(Idea::Table, Idea::Id)
+        .on_delete(on_delete)
+        .to_owned();
+
+    if manager.get_database_backend() == DbBackend::Sqlite {
+        // Sqlite can't alter columns or foreign keys, so the table has to be
+        // rebuilt. Migrations aren't run in a transaction there, and losing
+        // the comments halfway through would be bad
+        let txn = manager.get_connection().begin().await?;
+        rebuild_comments(&SchemaManager::new(&txn), comments_on, foreign_key).await?;
+        return txn.commit().await;
+    }
+
+    manager
+        .drop_foreign_key(
+            ForeignKey::drop()
+                .name(FOREIGN_KEY)
+                .table(Comment::Table)
+                .to_owned(),
+        )
+        .await?;
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Comment::Table)
+                .modify_column(&mut comments_on)
+                .to_owned(),
+        )
+        .await?;
+    manager.create_foreign_key(foreign_key).await
+}
+
+/// Recreates the comment table with `comments_on` and `foreign_key`,
+/// keeping the comments. Only needed on sqlite, and only safe to run in a
+/// transaction
+pub(crate) async fn rebuild_comments(
+    manager: &SchemaManager<'_>,
+    mut comments_on: ColumnDef,
+    mut foreign_key: ForeignKeyCreateStatement,
+) -> Result<(), DbErr> {
+    manager
+        .create_table(
+            Table::create()
+                .table(NewComment::Table)
+                .col(
+                    ColumnDef::new(Comment::Id)
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Comment::Author).string().not_null())
+                .col(ColumnDef::new(Comment::Time).timestamp().not_null())
+                .col(ColumnDef::new(Comment::Content).string().not_null())
+                .col(&mut comments_on)
+                .foreign_key(foreign_key.from_tbl(NewComment::Table))
+                .to_owned(),
+        )
+        .await?;
+
+    let columns = [
+        Comment::Id,
+        Comment::Author,
+        Comment::Time,
+        Comment::Content,
+        Comment::CommentsOn,
+    ];
+    let copy = Query::insert()
+        .into_table(NewComment::Table)
+        .columns(columns)
+        .select_from(
+            Query::select()
+                .columns(columns)
+                .from(Comment::Table)
+                .to_owned(),
+        )
+        .map_err(|err| DbErr::Migration(err.to_string()))?
+        .to_owned();
+    manager.exec_stmt(copy).await?;
+
+    manager
+        .drop_table(Table::drop().table(Comment::Table).to_owned())
+        .await?;
+    manager
+        .rename_table(
+            Table::rename()
+                .table(NewComment::Table, Comment::Table)
+                .to_owned(),
+        )
+        .await
+}
+
+/// Where the comments are copied to while the table is rebuilt
+#[derive(DeriveIden)]
+enum NewComment {
+    Table,
+}
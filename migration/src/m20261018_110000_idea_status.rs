@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .add_column(
+                        ColumnDef::new(IdeaStatus::Status)
+                            .string()
+                            .not_null()
+                            .default("New"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Idea::Table)
+                    .value(IdeaStatus::Status, "Resolved")
+                    .and_where(Expr::col(Idea::Solved).eq(true))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .drop_column(Idea::Solved)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StatusHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StatusHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(StatusHistory::Idea).integer().not_null())
+                    .col(ColumnDef::new(StatusHistory::Status).string().not_null())
+                    .col(ColumnDef::new(StatusHistory::ChangedBy).string().not_null())
+                    .col(ColumnDef::new(StatusHistory::Time).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-status_history-idea")
+                            .from(StatusHistory::Table, StatusHistory::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StatusHistory::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .add_column(
+                        ColumnDef::new(Idea::Solved)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Idea::Table)
+                    .value(Idea::Solved, true)
+                    .and_where(Expr::col(IdeaStatus::Status).eq("Resolved"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .drop_column(IdeaStatus::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// The column replacing `idea.solved`
+#[derive(DeriveIden)]
+enum IdeaStatus {
+    Status,
+}
+
+#[derive(DeriveIden)]
+pub enum StatusHistory {
+    Table,
+    Id,
+    Idea,
+    Status,
+    ChangedBy,
+    Time,
+}
@@ -0,0 +1,116 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The kinds every database starts out with. More can be added by inserting
+/// into the `kind` table
+const KINDS: [&str; 5] = [
+    "Issue",
+    "Improvement",
+    "Question",
+    "Praise",
+    "Safety concern",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Kind::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Kind::Name).string().not_null().primary_key())
+                    .col(ColumnDef::new(Kind::Position).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        let mut insert = Query::insert()
+            .into_table(Kind::Table)
+            .columns([Kind::Name, Kind::Position])
+            .to_owned();
+        for (position, name) in (0..).zip(KINDS) {
+            insert.values_panic([name.into(), position.into()]);
+        }
+        manager.exec_stmt(insert).await?;
+
+        // Sqlite already stores the kind as text, and adding the foreign key
+        // there would mean rebuilding the idea table
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE idea ALTER COLUMN kind TYPE varchar USING kind::text")
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-idea-kind")
+                    .from(Idea::Table, Idea::Kind)
+                    .to(Kind::Table, Kind::Name)
+                    .on_update(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(Type::drop().name(Issuekind).to_owned())
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The enum only knows the original two kinds
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Idea::Table)
+                    .value(Idea::Kind, "Issue")
+                    .and_where(Expr::col(Idea::Kind).is_not_in(["Issue", "Improvement"]))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .drop_foreign_key(
+                    ForeignKey::drop()
+                        .name("fk-idea-kind")
+                        .table(Idea::Table)
+                        .to_owned(),
+                )
+                .await?;
+            manager
+                .create_type(
+                    Type::create()
+                        .as_enum(Issuekind)
+                        .values([Alias::new("Issue"), Alias::new("Improvement")])
+                        .to_owned(),
+                )
+                .await?;
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "ALTER TABLE idea ALTER COLUMN kind TYPE issuekind USING kind::issuekind",
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Kind::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Kind {
+    Table,
+    Name,
+    Position,
+}
+
+/// The postgres enum the kind used to be stored as
+#[derive(DeriveIden)]
+struct Issuekind;
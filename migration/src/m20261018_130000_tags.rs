@@ -0,0 +1,90 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tag::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tag::Name).string().not_null().unique_key())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(IdeaTag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(IdeaTag::Idea).integer().not_null())
+                    .col(ColumnDef::new(IdeaTag::Tag).integer().not_null())
+                    .primary_key(Index::create().col(IdeaTag::Idea).col(IdeaTag::Tag))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-idea_tag-idea")
+                            .from(IdeaTag::Table, IdeaTag::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-idea_tag-tag")
+                            .from(IdeaTag::Table, IdeaTag::Tag)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Others see tags change live, see the notify_changes migration.
+        // The idea is sent, as that's what the tags are fetched by
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "CREATE TRIGGER idea_tag_notify AFTER INSERT OR UPDATE OR DELETE ON idea_tag \
+                     FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('idea')",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IdeaTag::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tag::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum IdeaTag {
+    Table,
+    Idea,
+    Tag,
+}
@@ -0,0 +1,58 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Vote::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Vote::Idea).integer().not_null())
+                    .col(ColumnDef::new(Vote::Voter).string().not_null())
+                    .col(ColumnDef::new(Vote::Time).timestamp().not_null())
+                    // Everyone gets one vote per idea
+                    .primary_key(Index::create().col(Vote::Idea).col(Vote::Voter))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vote-idea")
+                            .from(Vote::Table, Vote::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "CREATE TRIGGER vote_notify AFTER INSERT OR UPDATE OR DELETE ON vote \
+                     FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('idea')",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Vote::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Vote {
+    Table,
+    Idea,
+    Voter,
+    Time,
+}
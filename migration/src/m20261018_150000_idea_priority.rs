@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .add_column(
+                        ColumnDef::new(IdeaPriority::Priority)
+                            .string()
+                            .not_null()
+                            .default("Normal"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .drop_column(IdeaPriority::Priority)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IdeaPriority {
+    Priority,
+}
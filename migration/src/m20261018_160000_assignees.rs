@@ -0,0 +1,56 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Assignee::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Assignee::Idea).integer().not_null())
+                    .col(ColumnDef::new(Assignee::Name).string().not_null())
+                    // Nobody is assigned to the same idea twice
+                    .primary_key(Index::create().col(Assignee::Idea).col(Assignee::Name))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-assignee-idea")
+                            .from(Assignee::Table, Assignee::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "CREATE TRIGGER assignee_notify AFTER INSERT OR UPDATE OR DELETE ON assignee \
+                     FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('idea')",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Assignee::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Assignee {
+    Table,
+    Idea,
+    Name,
+}
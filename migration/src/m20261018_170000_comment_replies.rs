@@ -0,0 +1,79 @@
+use sea_orm::{DbBackend, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20240922_075048_create_ideas::Idea, m20241002_082310_create_comments::Comment,
+    m20261018_100000_cascade_comments::rebuild_comments,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut parent_id = ColumnDef::new(CommentParent::ParentId);
+        parent_id.integer().null();
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            // Sqlite can't add foreign keys to existing tables, but allows
+            // them on new columns
+            parent_id.extra("REFERENCES comment (id) ON DELETE CASCADE");
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comment::Table)
+                    .add_column(&mut parent_id)
+                    .to_owned(),
+            )
+            .await?;
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            return Ok(());
+        }
+
+        // Replies go away with what they reply to, like comments with their
+        // idea
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-comment-parent")
+                    .from(Comment::Table, CommentParent::ParentId)
+                    .to(Comment::Table, Comment::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            // The column can't be dropped while it's part of a foreign key,
+            // so the table is rebuilt the way the cascade migration left it
+            let mut comments_on = ColumnDef::new(Comment::CommentsOn);
+            comments_on.integer().not_null();
+            let foreign_key = ForeignKey::create()
+                .name("fk-comment-idea")
+                .from(Comment::Table, Comment::CommentsOn)
+                .to(Idea::Table, Idea::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .to_owned();
+            let txn = manager.get_connection().begin().await?;
+            rebuild_comments(&SchemaManager::new(&txn), comments_on, foreign_key).await?;
+            return txn.commit().await;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comment::Table)
+                    .drop_column(CommentParent::ParentId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CommentParent {
+    ParentId,
+}
@@ -0,0 +1,152 @@
+use sea_orm_migration::prelude::*;
+
+use crate::{m20240922_075048_create_ideas::Idea, m20241002_082310_create_comments::Comment};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IdeaRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IdeaRevision::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(IdeaRevision::Idea).integer().not_null())
+                    .col(ColumnDef::new(IdeaRevision::Title).string().not_null())
+                    .col(
+                        ColumnDef::new(IdeaRevision::Description)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(IdeaRevision::Author).string().not_null())
+                    .col(ColumnDef::new(IdeaRevision::Kind).string().not_null())
+                    .col(ColumnDef::new(IdeaRevision::Editor).string().not_null())
+                    .col(ColumnDef::new(IdeaRevision::Time).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-idea_revision-idea")
+                            .from(IdeaRevision::Table, IdeaRevision::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentRevision::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CommentRevision::Comment)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CommentRevision::Author).string().not_null())
+                    .col(ColumnDef::new(CommentRevision::Content).string().not_null())
+                    .col(ColumnDef::new(CommentRevision::Editor).string().not_null())
+                    .col(ColumnDef::new(CommentRevision::Time).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-comment_revision-comment")
+                            .from(CommentRevision::Table, CommentRevision::Comment)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Sqlite can only add one column per statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .add_column(ColumnDef::new(Edited::EditedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comment::Table)
+                    .add_column(ColumnDef::new(Edited::EditedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comment::Table)
+                    .drop_column(Edited::EditedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .drop_column(Edited::EditedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CommentRevision::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(IdeaRevision::Table).to_owned())
+            .await
+    }
+}
+
+/// A version of an idea that was replaced by an edit
+#[derive(DeriveIden)]
+enum IdeaRevision {
+    Table,
+    Id,
+    Idea,
+    Title,
+    Description,
+    Author,
+    Kind,
+    /// Who made the edit that replaced this version
+    Editor,
+    /// When the edit was made
+    Time,
+}
+
+/// A version of a comment that was replaced by an edit
+#[derive(DeriveIden)]
+enum CommentRevision {
+    Table,
+    Id,
+    Comment,
+    Author,
+    Content,
+    Editor,
+    Time,
+}
+
+#[derive(DeriveIden)]
+enum Edited {
+    EditedAt,
+}
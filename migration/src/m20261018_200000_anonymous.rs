@@ -0,0 +1,138 @@
+use sea_orm::{DbBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .add_column(
+                        ColumnDef::new(Anonymous::Anonymous)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Kept apart from the idea, so it's only read when a moderator asks
+        // for it
+        manager
+            .create_table(
+                Table::create()
+                    .table(IdeaIdentity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IdeaIdentity::Idea)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(IdeaIdentity::Author).string().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-idea_identity-idea")
+                            .from(IdeaIdentity::Table, IdeaIdentity::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Moderator::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Moderator::Name)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            moderator_role(manager).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Moderator::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(IdeaIdentity::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Idea::Table)
+                    .drop_column(Anonymous::Anonymous)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Anonymous {
+    Anonymous,
+}
+
+#[derive(DeriveIden)]
+enum IdeaIdentity {
+    Table,
+    Idea,
+    Author,
+}
+
+#[derive(DeriveIden)]
+enum Moderator {
+    Table,
+    Name,
+}
+
+/// Lets only the members of the `verneanbud_moderator` role read who
+/// submitted the anonymous ideas. Creating the role needs the right to create
+/// roles, and without it the moderator table decides instead
+async fn moderator_role(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    // A failing statement would abort the transaction the migration runs in,
+    // so the error is caught by the database itself
+    db.execute_unprepared(
+        "DO $$ BEGIN \
+         IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = 'verneanbud_moderator') THEN \
+         CREATE ROLE verneanbud_moderator NOLOGIN; \
+         END IF; \
+         GRANT SELECT ON idea_identity TO verneanbud_moderator; \
+         EXCEPTION WHEN insufficient_privilege THEN NULL; \
+         END $$",
+    )
+    .await?;
+    let role = db
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT FROM pg_roles WHERE rolname = 'verneanbud_moderator'",
+        ))
+        .await?;
+    if role.is_none() {
+        println!(
+            "Warning: not allowed to create the role verneanbud_moderator, so the moderator \
+             table decides who may see the authors of anonymous ideas. See the README"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use sea_orm::{ConnectionTrait, DbBackend, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use crate::{m20240922_075048_create_ideas::Idea, m20241002_082310_create_comments::Comment};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The name given to the authors that were left empty
+const UNKNOWN: &str = "Unknown";
+
+/// The tables with an author, which is a column of that name in all of them
+const TABLES: [&str; 2] = ["idea", "comment"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            // Migrations aren't run in a transaction there, and losing the
+            // authors halfway through would be bad
+            let txn = manager.get_connection().begin().await?;
+            to_people(&SchemaManager::new(&txn)).await?;
+            return txn.commit().await;
+        }
+        to_people(manager).await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TRIGGER person_notify AFTER INSERT OR UPDATE OR DELETE ON person \
+                 FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('id')",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(ColumnDef::new(Idea::Author).string().not_null().default(""))
+                        .to_owned(),
+                )
+                .await?;
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET author = \
+                 (SELECT name FROM person WHERE person.id = {table}.author_id) \
+                 WHERE author_id IS NOT NULL"
+            ))
+            .await?;
+        }
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Idea::Table)
+                    .value(Idea::Author, "Anonymous")
+                    .and_where(Expr::col(People::Anonymous).eq(true))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Sqlite can't drop columns that are part of a foreign key. The
+        // people are left behind unused, and picked up again by `up`
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            return Ok(());
+        }
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(People::AuthorId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .drop_table(Table::drop().table(Person::Table).to_owned())
+            .await
+    }
+}
+
+/// Creates a person for every author, and points the ideas and comments at
+/// them instead of spelling out their name
+async fn to_people(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let backend = manager.get_database_backend();
+    manager
+        .create_table(
+            Table::create()
+                .table(Person::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(Person::Id)
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(
+                    ColumnDef::new(Person::Name)
+                        .string()
+                        .not_null()
+                        .unique_key(),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+    // Anonymous ideas are kept that way. Who really wrote them stays a name in
+    // idea_identity, which most can't read
+    let mut names = Vec::new();
+    for select in [
+        Query::select()
+            .column(Idea::Author)
+            .from(Idea::Table)
+            .and_where(Expr::col(People::Anonymous).eq(false))
+            .to_owned(),
+        Query::select()
+            .column(Comment::Author)
+            .from(Comment::Table)
+            .to_owned(),
+    ] {
+        for row in db.query_all(backend.build(&select)).await? {
+            names.push(row.try_get::<String>("", "author")?);
+        }
+    }
+    let people = dedup(&names);
+
+    // Sqlite keeps the people of an earlier `down` around
+    let mut ids = HashMap::new();
+    let select = Query::select()
+        .columns([Person::Id, Person::Name])
+        .from(Person::Table)
+        .to_owned();
+    for row in db.query_all(backend.build(&select)).await? {
+        let name = row.try_get::<String>("", "name")?;
+        ids.insert(key(&name), row.try_get::<i32>("", "id")?);
+    }
+    for (key, (name, _)) in &people {
+        if ids.contains_key(key) {
+            continue;
+        }
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Person::Table)
+                    .columns([Person::Name])
+                    .values_panic([name.into()])
+                    .to_owned(),
+            )
+            .await?;
+        let select = Query::select()
+            .column(Person::Id)
+            .from(Person::Table)
+            .and_where(Expr::col(Person::Name).eq(name))
+            .to_owned();
+        let row = db
+            .query_one(backend.build(&select))
+            .await?
+            .ok_or_else(|| DbErr::Migration(format!("the person {name:?} wasn't inserted")))?;
+        ids.insert(key.clone(), row.try_get::<i32>("", "id")?);
+    }
+
+    for table in TABLES {
+        let foreign_key = format!("fk-{table}-author");
+        let is_idea = table == "idea";
+        let table = Alias::new(table);
+        if !manager.has_column(table.to_string(), "author_id").await? {
+            let mut author_id = ColumnDef::new(People::AuthorId);
+            author_id.integer().null();
+            if backend == DbBackend::Sqlite {
+                // Sqlite can't add foreign keys to existing tables, but
+                // allows them on new columns
+                author_id.extra("REFERENCES person (id)");
+            }
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table.clone())
+                        .add_column(&mut author_id)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for (key, (_, spellings)) in &people {
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(table.clone())
+                        .value(People::AuthorId, ids[key])
+                        .and_where(Expr::col(Idea::Author).is_in(spellings.clone()))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if is_idea {
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(table.clone())
+                        .value(People::AuthorId, None::<i32>)
+                        .and_where(Expr::col(People::Anonymous).eq(true))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(table.clone())
+                    .drop_column(Idea::Author)
+                    .to_owned(),
+            )
+            .await?;
+        if backend == DbBackend::Sqlite {
+            continue;
+        }
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name(foreign_key)
+                    .from(table.clone(), People::AuthorId)
+                    .to(Person::Table, Person::Id)
+                    .to_owned(),
+            )
+            .await?;
+        // Only anonymous ideas have no author
+        if !is_idea {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .modify_column(ColumnDef::new(People::AuthorId).integer().not_null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Groups the names that only differ in case or whitespace, like "Ola" and
+/// " ola". Every group becomes a single person, named the way most of them
+/// were spelled. The groups are keyed by [`key`], and hold that name and
+/// every spelling of it
+pub(crate) fn dedup(names: &[String]) -> HashMap<String, (String, Vec<String>)> {
+    let mut counts = HashMap::<_, HashMap<_, usize>>::new();
+    for name in names {
+        *counts
+            .entry(key(name))
+            .or_default()
+            .entry(name.clone())
+            .or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(key, spellings)| {
+            let name = spellings
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|x| x.0.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|x| !x.is_empty())
+                .unwrap_or_else(|| UNKNOWN.to_string());
+            (key, (name, spellings.into_keys().collect()))
+        })
+        .collect()
+}
+
+/// What two names have in common if they belong to the same person
+pub(crate) fn key(name: &str) -> String {
+    let key = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if key.is_empty() {
+        UNKNOWN.to_lowercase()
+    } else {
+        key
+    }
+}
+
+#[derive(DeriveIden)]
+pub(crate) enum Person {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum People {
+    AuthorId,
+    Anonymous,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dedup, key};
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn keys_ignore_whitespace_and_case() {
+        assert_eq!(key(" Ola  Nordmann "), "ola nordmann");
+        assert_eq!(key("OLA\tnordmann"), "ola nordmann");
+        assert_eq!(key("  "), "unknown");
+    }
+
+    #[test]
+    fn variants_become_one_person() {
+        let people = dedup(&names(&["Ola", " ola", "OLA ", "Kari"]));
+        assert_eq!(people.len(), 2);
+        let (_, mut spellings) = people["ola"].clone();
+        spellings.sort();
+        assert_eq!(spellings, names(&[" ola", "OLA ", "Ola"]));
+    }
+
+    #[test]
+    fn majority_spelling_wins() {
+        let people = dedup(&names(&[
+            "ola",
+            "Ola  Nordmann",
+            "ola nordmann",
+            "Ola  Nordmann",
+        ]));
+        assert_eq!(people["ola nordmann"].0, "Ola Nordmann");
+        // A tie goes to the spelling that sorts first
+        let people = dedup(&names(&["ola", "Ola"]));
+        assert_eq!(people["ola"].0, "Ola");
+    }
+
+    #[test]
+    fn empty_names_become_unknown() {
+        let people = dedup(&names(&["", "  "]));
+        assert_eq!(people.len(), 1);
+        assert_eq!(people["unknown"].0, "Unknown");
+    }
+}
@@ -0,0 +1,115 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+use crate::{m20240922_075048_create_ideas::Idea, m20261018_210000_people::Person};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Roster::Table)
+                    .if_not_exists()
+                    // Everyone is on the roster once
+                    .col(
+                        ColumnDef::new(Roster::Person)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Roster::Class)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(Roster::ContactedAt).timestamp().null())
+                    .col(ColumnDef::new(Roster::ContactedBy).string().null())
+                    .col(ColumnDef::new(Roster::Notes).text().not_null().default(""))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-roster-person")
+                            .from(Roster::Table, Roster::Person)
+                            .to(Person::Table, Person::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RosterIdea::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RosterIdea::Person).integer().not_null())
+                    .col(ColumnDef::new(RosterIdea::Idea).integer().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(RosterIdea::Person)
+                            .col(RosterIdea::Idea),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-roster_idea-person")
+                            .from(RosterIdea::Table, RosterIdea::Person)
+                            .to(Roster::Table, Roster::Person)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-roster_idea-idea")
+                            .from(RosterIdea::Table, RosterIdea::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let db = manager.get_connection();
+            db.execute_unprepared(
+                "CREATE TRIGGER roster_notify AFTER INSERT OR UPDATE OR DELETE ON roster \
+                 FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('person')",
+            )
+            .await?;
+            db.execute_unprepared(
+                "CREATE TRIGGER roster_idea_notify AFTER INSERT OR UPDATE OR DELETE ON roster_idea \
+                 FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('person')",
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RosterIdea::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Roster::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Roster {
+    Table,
+    Person,
+    Class,
+    ContactedAt,
+    ContactedBy,
+    Notes,
+}
+
+#[derive(DeriveIden)]
+enum RosterIdea {
+    Table,
+    Person,
+    Idea,
+}
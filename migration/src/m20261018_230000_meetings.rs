@@ -0,0 +1,149 @@
+use sea_orm::DbBackend;
+use sea_orm_migration::prelude::*;
+
+use crate::m20240922_075048_create_ideas::Idea;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Meeting::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Meeting::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Meeting::Title).string().not_null())
+                    .col(ColumnDef::new(Meeting::Time).timestamp().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MeetingAttendee::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MeetingAttendee::Meeting)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MeetingAttendee::Name).string().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(MeetingAttendee::Meeting)
+                            .col(MeetingAttendee::Name),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-meeting_attendee-meeting")
+                            .from(MeetingAttendee::Table, MeetingAttendee::Meeting)
+                            .to(Meeting::Table, Meeting::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AgendaItem::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AgendaItem::Meeting).integer().not_null())
+                    .col(ColumnDef::new(AgendaItem::Idea).integer().not_null())
+                    .col(ColumnDef::new(AgendaItem::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(AgendaItem::Decision)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    // An idea is on the agenda of a meeting once
+                    .primary_key(
+                        Index::create()
+                            .col(AgendaItem::Meeting)
+                            .col(AgendaItem::Idea),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-agenda_item-meeting")
+                            .from(AgendaItem::Table, AgendaItem::Meeting)
+                            .to(Meeting::Table, Meeting::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-agenda_item-idea")
+                            .from(AgendaItem::Table, AgendaItem::Idea)
+                            .to(Idea::Table, Idea::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let db = manager.get_connection();
+            // All of them send the id of the meeting, which is reloaded as a
+            // whole
+            for (table, column) in [
+                ("meeting", "id"),
+                ("meeting_attendee", "meeting"),
+                ("agenda_item", "meeting"),
+            ] {
+                db.execute_unprepared(&format!(
+                    "CREATE TRIGGER {table}_notify AFTER INSERT OR UPDATE OR DELETE ON {table} \
+                     FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('{column}')"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AgendaItem::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MeetingAttendee::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Meeting::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub(crate) enum Meeting {
+    Table,
+    Id,
+    Title,
+    Time,
+}
+
+#[derive(DeriveIden)]
+pub(crate) enum MeetingAttendee {
+    Table,
+    Meeting,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum AgendaItem {
+    Table,
+    Meeting,
+    Idea,
+    Position,
+    Decision,
+}
@@ -0,0 +1,211 @@
+use std::collections::{BTreeSet, HashMap};
+
+use sea_orm::{ConnectionTrait, DbBackend, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20261018_210000_people::{dedup, key, Person},
+    m20261018_230000_meetings::{Meeting, MeetingAttendee},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            // Migrations aren't run in a transaction there, and losing the
+            // attendees halfway through would be bad
+            let txn = manager.get_connection().begin().await?;
+            to_people(&SchemaManager::new(&txn)).await?;
+            return txn.commit().await;
+        }
+        to_people(manager).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let select = Query::select()
+            .column((MeetingAttendee::Table, MeetingAttendee::Meeting))
+            .column((Person::Table, Person::Name))
+            .from(MeetingAttendee::Table)
+            .inner_join(
+                Person::Table,
+                Expr::col((Person::Table, Person::Id))
+                    .equals((MeetingAttendee::Table, Attendee::Person)),
+            )
+            .to_owned();
+        let mut attendees = Vec::new();
+        for row in db.query_all(backend.build(&select)).await? {
+            attendees.push((
+                row.try_get::<i32>("", "meeting")?,
+                row.try_get::<String>("", "name")?,
+            ));
+        }
+
+        let mut name = ColumnDef::new(MeetingAttendee::Name);
+        name.string().not_null();
+        recreate(manager, &mut name, None).await?;
+        for (meeting, name) in attendees {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(MeetingAttendee::Table)
+                        .columns([MeetingAttendee::Meeting, MeetingAttendee::Name])
+                        .values_panic([meeting.into(), name.into()])
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Makes a person of every attendee, and points the meetings at them instead
+/// of spelling out their name
+async fn to_people(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let select = Query::select()
+        .columns([MeetingAttendee::Meeting, MeetingAttendee::Name])
+        .from(MeetingAttendee::Table)
+        .to_owned();
+    let mut attendees = Vec::new();
+    for row in db.query_all(backend.build(&select)).await? {
+        attendees.push((
+            row.try_get::<i32>("", "meeting")?,
+            row.try_get::<String>("", "name")?,
+        ));
+    }
+    let people = dedup(&attendees.iter().map(|x| x.1.clone()).collect::<Vec<_>>());
+
+    let mut ids = HashMap::new();
+    let select = Query::select()
+        .columns([Person::Id, Person::Name])
+        .from(Person::Table)
+        .to_owned();
+    for row in db.query_all(backend.build(&select)).await? {
+        let name = row.try_get::<String>("", "name")?;
+        ids.insert(key(&name), row.try_get::<i32>("", "id")?);
+    }
+    for (key, (name, _)) in &people {
+        if ids.contains_key(key) {
+            continue;
+        }
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Person::Table)
+                    .columns([Person::Name])
+                    .values_panic([name.into()])
+                    .to_owned(),
+            )
+            .await?;
+        let select = Query::select()
+            .column(Person::Id)
+            .from(Person::Table)
+            .and_where(Expr::col(Person::Name).eq(name))
+            .to_owned();
+        let row = db
+            .query_one(backend.build(&select))
+            .await?
+            .ok_or_else(|| DbErr::Migration(format!("the person {name:?} wasn't inserted")))?;
+        ids.insert(key.clone(), row.try_get::<i32>("", "id")?);
+    }
+
+    // Spellings of the same person at the same meeting become one attendee
+    let attendees = attendees
+        .into_iter()
+        .map(|(meeting, name)| (meeting, ids[&key(&name)]))
+        .collect::<BTreeSet<_>>();
+
+    let mut person = ColumnDef::new(Attendee::Person);
+    person.integer().not_null();
+    recreate(
+        manager,
+        &mut person,
+        Some(
+            ForeignKey::create()
+                .name("fk-meeting_attendee-person")
+                .from(MeetingAttendee::Table, Attendee::Person)
+                .to(Person::Table, Person::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .to_owned(),
+        ),
+    )
+    .await?;
+    for (meeting, person) in attendees {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(MeetingAttendee::Table)
+                    .columns([
+                        MeetingAttendee::Meeting.into_iden(),
+                        Attendee::Person.into_iden(),
+                    ])
+                    .values_panic([meeting.into(), person.into()])
+                    .to_owned(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Replaces the attendees by an empty table that tells who attends with
+/// `column`. Neither database can change the primary key of a table in
+/// place
+async fn recreate(
+    manager: &SchemaManager<'_>,
+    column: &mut ColumnDef,
+    foreign_key: Option<ForeignKeyCreateStatement>,
+) -> Result<(), DbErr> {
+    manager
+        .drop_table(Table::drop().table(MeetingAttendee::Table).to_owned())
+        .await?;
+    let name = column.get_column_name();
+    let mut table = Table::create();
+    table
+        .table(MeetingAttendee::Table)
+        .col(
+            ColumnDef::new(MeetingAttendee::Meeting)
+                .integer()
+                .not_null(),
+        )
+        .col(column)
+        .primary_key(
+            Index::create()
+                .col(MeetingAttendee::Meeting)
+                .col(Alias::new(name)),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .name("fk-meeting_attendee-meeting")
+                .from(MeetingAttendee::Table, MeetingAttendee::Meeting)
+                .to(Meeting::Table, Meeting::Id)
+                .on_delete(ForeignKeyAction::Cascade),
+        );
+    if let Some(mut foreign_key) = foreign_key {
+        table.foreign_key(&mut foreign_key);
+    }
+    manager.create_table(table.to_owned()).await?;
+
+    if manager.get_database_backend() == DbBackend::Postgres {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TRIGGER meeting_attendee_notify \
+                 AFTER INSERT OR UPDATE OR DELETE ON meeting_attendee \
+                 FOR EACH ROW EXECUTE FUNCTION verneanbud_notify('meeting')",
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[derive(DeriveIden)]
+enum Attendee {
+    Person,
+}
@@ -7,22 +7,44 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub author: String,
+    pub author_id: i32,
     pub time: DateTime,
     pub content: String,
     pub comments_on: i32,
+    /// The comment this one replies to, if any
+    pub parent_id: Option<i32>,
+    /// When the comment was last edited, if ever
+    pub edited_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::AuthorId",
+        to = "super::person::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Person,
     #[sea_orm(
         belongs_to = "super::idea::Entity",
         from = "Column::CommentsOn",
         to = "super::idea::Column::Id",
         on_update = "NoAction",
-        on_delete = "NoAction"
+        on_delete = "Cascade"
     )]
     Idea,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Parent,
+    #[sea_orm(has_many = "super::comment_revision::Entity")]
+    CommentRevision,
 }
 
 impl Related<super::idea::Entity> for Entity {
@@ -31,4 +53,16 @@ impl Related<super::idea::Entity> for Entity {
     }
 }
 
+impl Related<super::comment_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CommentRevision.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,6 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
-use super::sea_orm_active_enums::Issuekind;
+use super::sea_orm_active_enums::{Priority, Status};
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -10,16 +10,73 @@ pub struct Model {
     pub id: i32,
     pub title: String,
     pub description: String,
-    pub author: String,
-    pub solved: bool,
-    pub kind: Issuekind,
+    /// Who submitted the idea. `None` if it was submitted anonymously
+    pub author_id: Option<i32>,
+    pub status: Status,
+    pub priority: Priority,
+    pub kind: String,
     pub time: DateTime,
+    /// When the idea was last edited, if ever
+    pub edited_at: Option<DateTime>,
+    /// When the idea was moved to the trash, if it was
+    pub deleted_at: Option<DateTime>,
+    /// Whether the idea was submitted anonymously. Who really submitted it
+    /// is then kept in [`super::idea_identity`], if they gave their name at
+    /// all
+    pub anonymous: bool,
+    /// When the idea has to be followed up, if it has to be
+    pub due: Option<DateTime>,
+}
+
+/// The author shown for anonymous ideas
+pub const ANONYMOUS: &str = "Anonymous";
+
+impl Model {
+    /// Whether the idea should have been followed up before `today`, and
+    /// still hasn't been dealt with
+    pub fn is_overdue(&self, today: Date) -> bool {
+        self.status.is_open()
+            && self.deleted_at.is_none()
+            && self.due.is_some_and(|due| due.date() < today)
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::comment::Entity")]
     Comment,
+    #[sea_orm(has_many = "super::status_history::Entity")]
+    StatusHistory,
+    #[sea_orm(
+        belongs_to = "super::kind::Entity",
+        from = "Column::Kind",
+        to = "super::kind::Column::Name",
+        on_update = "Cascade",
+        on_delete = "NoAction"
+    )]
+    Kind,
+    #[sea_orm(has_many = "super::idea_tag::Entity")]
+    IdeaTag,
+    #[sea_orm(has_many = "super::vote::Entity")]
+    Vote,
+    #[sea_orm(has_many = "super::assignee::Entity")]
+    Assignee,
+    #[sea_orm(has_many = "super::idea_revision::Entity")]
+    IdeaRevision,
+    #[sea_orm(has_one = "super::idea_identity::Entity")]
+    IdeaIdentity,
+    #[sea_orm(has_many = "super::roster_idea::Entity")]
+    RosterIdea,
+    #[sea_orm(has_many = "super::agenda_item::Entity")]
+    AgendaItem,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::AuthorId",
+        to = "super::person::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Person,
 }
 
 impl Related<super::comment::Entity> for Entity {
@@ -28,4 +85,73 @@ impl Related<super::comment::Entity> for Entity {
     }
 }
 
+impl Related<super::status_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StatusHistory.def()
+    }
+}
+
+impl Related<super::kind::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Kind.def()
+    }
+}
+
+impl Related<super::idea_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IdeaTag.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::idea_tag::Relation::Tag.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::idea_tag::Relation::Idea.def().rev())
+    }
+}
+
+impl Related<super::vote::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vote.def()
+    }
+}
+
+impl Related<super::assignee::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assignee.def()
+    }
+}
+
+impl Related<super::idea_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IdeaRevision.def()
+    }
+}
+
+impl Related<super::idea_identity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IdeaIdentity.def()
+    }
+}
+
+impl Related<super::agenda_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AgendaItem.def()
+    }
+}
+
+impl Related<super::roster_idea::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RosterIdea.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,6 +2,22 @@
 
 pub mod prelude;
 
+pub mod agenda_item;
+pub mod assignee;
 pub mod comment;
+pub mod comment_revision;
 pub mod idea;
+pub mod idea_identity;
+pub mod idea_revision;
+pub mod idea_tag;
+pub mod kind;
+pub mod meeting;
+pub mod meeting_attendee;
+pub mod moderator;
+pub mod person;
+pub mod roster;
+pub mod roster_idea;
 pub mod sea_orm_active_enums;
+pub mod status_history;
+pub mod tag;
+pub mod vote;
@@ -1,4 +1,20 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
+pub use super::agenda_item::Entity as AgendaItem;
+pub use super::assignee::Entity as Assignee;
 pub use super::comment::Entity as Comment;
+pub use super::comment_revision::Entity as CommentRevision;
 pub use super::idea::Entity as Idea;
+pub use super::idea_identity::Entity as IdeaIdentity;
+pub use super::idea_revision::Entity as IdeaRevision;
+pub use super::idea_tag::Entity as IdeaTag;
+pub use super::kind::Entity as Kind;
+pub use super::meeting::Entity as Meeting;
+pub use super::meeting_attendee::Entity as MeetingAttendee;
+pub use super::moderator::Entity as Moderator;
+pub use super::person::Entity as Person;
+pub use super::roster::Entity as Roster;
+pub use super::roster_idea::Entity as RosterIdea;
+pub use super::status_history::Entity as StatusHistory;
+pub use super::tag::Entity as Tag;
+pub use super::vote::Entity as Vote;
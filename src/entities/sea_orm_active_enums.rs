@@ -1,12 +1,123 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
-use sea_orm::entity::prelude::*;
-
-#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
-#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "issuekind")]
-pub enum Issuekind {
-    #[sea_orm(string_value = "Improvement")]
-    Improvement,
-    #[sea_orm(string_value = "Issue")]
-    Issue,
+use sea_orm::{entity::prelude::*, Iterable};
+
+/// Where an idea is in the workflow. Stored as text, so adding a status
+/// doesn't need a migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Status {
+    #[sea_orm(string_value = "New")]
+    New,
+    #[sea_orm(string_value = "Triaged")]
+    Triaged,
+    #[sea_orm(string_value = "InProgress")]
+    InProgress,
+    #[sea_orm(string_value = "Resolved")]
+    Resolved,
+    #[sea_orm(string_value = "Rejected")]
+    Rejected,
+    #[sea_orm(string_value = "Duplicate")]
+    Duplicate,
+}
+
+impl Status {
+    /// How the status is shown to the user
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::New => "New",
+            Self::Triaged => "Triaged",
+            Self::InProgress => "In progress",
+            Self::Resolved => "Resolved",
+            Self::Rejected => "Rejected",
+            Self::Duplicate => "Duplicate",
+        }
+    }
+
+    /// The next step of the workflow. Ideas that were closed without being
+    /// resolved are reopened
+    pub const fn next(self) -> Self {
+        match self {
+            Self::New => Self::Triaged,
+            Self::Triaged => Self::InProgress,
+            Self::InProgress | Self::Resolved => Self::Resolved,
+            Self::Rejected | Self::Duplicate => Self::New,
+        }
+    }
+
+    /// The previous step of the workflow
+    pub const fn prev(self) -> Self {
+        match self {
+            Self::New | Self::Triaged | Self::Rejected | Self::Duplicate => Self::New,
+            Self::InProgress => Self::Triaged,
+            Self::Resolved => Self::InProgress,
+        }
+    }
+
+    /// Whether someone still has to deal with the idea
+    pub const fn is_open(self) -> bool {
+        matches!(self, Self::New | Self::Triaged | Self::InProgress)
+    }
+
+    /// Closes the idea without resolving it, alternating between the ways
+    /// of doing that
+    pub const fn reject(self) -> Self {
+        match self {
+            Self::Rejected => Self::Duplicate,
+            _ => Self::Rejected,
+        }
+    }
+}
+
+/// How urgently an idea should be dealt with. Ordered from least to most
+/// urgent
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum Priority {
+    #[sea_orm(string_value = "Low")]
+    Low,
+    #[default]
+    #[sea_orm(string_value = "Normal")]
+    Normal,
+    #[sea_orm(string_value = "High")]
+    High,
+    #[sea_orm(string_value = "Urgent")]
+    Urgent,
+}
+
+impl Priority {
+    /// How the priority is shown to the user
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Normal => "Normal",
+            Self::High => "High",
+            Self::Urgent => "Urgent",
+        }
+    }
+
+    /// One step more urgent, stopping at the most urgent
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Normal,
+            Self::Normal => Self::High,
+            Self::High | Self::Urgent => Self::Urgent,
+        }
+    }
+
+    /// One step less urgent, stopping at the least urgent
+    pub const fn prev(self) -> Self {
+        match self {
+            Self::Low | Self::Normal => Self::Low,
+            Self::High => Self::Normal,
+            Self::Urgent => Self::High,
+        }
+    }
+
+    /// The priority called `name`, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|x| x.name().eq_ignore_ascii_case(name))
+    }
 }
@@ -0,0 +1,86 @@
+//! Keeps track of everything that went wrong while talking to the db, so it
+//! can be shown to the user instead of ending the session
+use std::time::{Duration, Instant};
+
+use sea_orm::{prelude::DateTime, sqlx::types::chrono};
+
+use crate::app::DbActionParts;
+
+/// How long the latest error is shown in the status bar
+pub(crate) const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// All errors that have happened, newest last
+#[derive(Default)]
+pub struct ErrorLog {
+    entries: Vec<Entry>,
+    /// Whether the log is shown as a popup
+    pub open: bool,
+    /// The selected entry, counted from the newest one
+    pub selected: usize,
+    /// When the latest entry was added
+    last_added: Option<Instant>,
+}
+
+/// Something that went wrong
+pub struct Entry {
+    /// What was being done, e.g. `"Adding a comment"`
+    pub operation: String,
+    pub error: String,
+    pub time: DateTime,
+    pub(crate) retry: Option<Retry>,
+}
+
+/// What to do if the user wants to try again
+pub(crate) enum Retry {
+    /// Run the action with the id again
+    DbAction(usize, DbActionParts<'static>),
+    /// Fetch everything from the db again
+    Refresh,
+}
+
+impl ErrorLog {
+    pub(crate) fn push(&mut self, operation: String, error: String, retry: Option<Retry>) {
+        self.entries.push(Entry {
+            operation,
+            error,
+            time: chrono::Local::now().naive_local(),
+            retry,
+        });
+        self.last_added = Some(Instant::now());
+        self.selected = 0;
+    }
+
+    /// The newest entry, if it was added recently enough to still be shown
+    pub fn toast(&self) -> Option<&Entry> {
+        self.last_added
+            .filter(|x| x.elapsed() < TOAST_DURATION)
+            .and(self.entries.last())
+    }
+
+    /// All entries, newest first
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Removes the selected entry from the log
+    pub(crate) fn take_selected(&mut self) -> Option<Entry> {
+        let index = self.entries.len().checked_sub(self.selected + 1)?;
+        let entry = self.entries.remove(index);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        self.last_added = None;
+        Some(entry)
+    }
+
+    /// Hides the toast without removing anything
+    pub fn dismiss_toast(&mut self) {
+        self.last_added = None;
+    }
+}
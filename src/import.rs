@@ -0,0 +1,253 @@
+//! Puts the people in a class list on the roster
+//!
+//! The list is a CSV file with a header line naming the columns. `name` is
+//! required, `class` and `notes` are optional and anything else is ignored.
+//! Both `,` and `;` work as separators
+use core::fmt;
+use std::{
+    fs, io, mem,
+    path::{Path, PathBuf},
+};
+
+use sea_orm::{ActiveValue, DatabaseConnection, DbErr, EntityTrait, TransactionTrait};
+
+use crate::{
+    entities::{prelude::Roster, roster},
+    view_data::people::{find_or_create, normalize},
+};
+
+/// How the roster changed by importing a list
+#[derive(Debug, Default)]
+pub struct Imported {
+    /// How many people were put on the roster
+    pub added: usize,
+    /// How many were on it already, and got their class updated if the list
+    /// has classes
+    pub updated: usize,
+}
+
+/// Puts everyone in the class list at `path` on the roster, adding the
+/// people that don't exist yet. People that are on it already keep their
+/// notes and whether they were talked to. Nothing is imported if anything
+/// goes wrong
+pub async fn roster(db: &DatabaseConnection, path: &Path) -> Result<Imported, ImportError> {
+    let content = fs::read_to_string(path).map_err(|err| ImportError::Io {
+        path: path.to_path_buf(),
+        err,
+    })?;
+    let mut rows = parse(&content)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| ImportError::Invalid(1, "the file is empty".to_string()))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|x| x.trim().eq_ignore_ascii_case(name))
+    };
+    let name_column = column("name").ok_or_else(|| {
+        ImportError::Invalid(
+            1,
+            "the first line has to name the columns, including name".into(),
+        )
+    })?;
+    let class_column = column("class");
+    let notes_column = column("notes");
+
+    let mut imported = Imported::default();
+    let txn = db.begin().await?;
+    for row in rows {
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|x| row.get(x))
+                .map_or(String::new(), |x| x.trim().to_string())
+        };
+        let name = normalize(&field(Some(name_column)));
+        if name.is_empty() {
+            continue;
+        }
+        let person = find_or_create(&txn, &name).await?;
+        // A list without classes leaves the classes people have alone
+        let class = class_column.map(|_| field(class_column));
+        if Roster::find_by_id(person).one(&txn).await?.is_some() {
+            if let Some(class) = class {
+                Roster::update(roster::ActiveModel {
+                    person: ActiveValue::Unchanged(person),
+                    class: ActiveValue::Set(class),
+                    ..Default::default()
+                })
+                .exec(&txn)
+                .await?;
+            }
+            imported.updated += 1;
+        } else {
+            Roster::insert(roster::ActiveModel {
+                person: ActiveValue::Set(person),
+                class: ActiveValue::Set(class.unwrap_or_default()),
+                contacted_at: ActiveValue::Set(None),
+                contacted_by: ActiveValue::Set(None),
+                notes: ActiveValue::Set(field(notes_column)),
+            })
+            .exec(&txn)
+            .await?;
+            imported.added += 1;
+        }
+    }
+    txn.commit().await?;
+    Ok(imported)
+}
+
+/// Splits the CSV `content` into lines of fields. Fields can be quoted with
+/// `"`, which allows separators, newlines and doubled quotes in them. Empty
+/// lines are skipped
+fn parse(content: &str) -> Result<Vec<Vec<String>>, ImportError> {
+    // Spreadsheets like to start their exports with a byte order mark
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let header = content.lines().next().unwrap_or_default();
+    let separator = if header.matches(';').count() > header.matches(',').count() {
+        ';'
+    } else {
+        ','
+    };
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            '\n' if quoted => {
+                line += 1;
+                field.push('\n');
+            }
+            '\r' if !quoted => (),
+            '\n' => {
+                line += 1;
+                row.push(mem::take(&mut field));
+                if row.iter().any(|x| !x.trim().is_empty()) {
+                    rows.push(mem::take(&mut row));
+                }
+                row.clear();
+            }
+            c if c == separator && !quoted => row.push(mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(ImportError::Invalid(line, "a quote is never closed".into()));
+    }
+    row.push(field);
+    if row.iter().any(|x| !x.trim().is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Why a class list couldn't be imported
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file couldn't be read
+    Io { path: PathBuf, err: io::Error },
+    /// The file isn't a class list. The line it went wrong on, and why
+    Invalid(usize, String),
+    /// Saving the roster failed
+    Db(DbErr),
+}
+
+impl From<DbErr> for ImportError {
+    fn from(value: DbErr) -> Self {
+        Self::Db(value)
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, err } => write!(f, "could not read {}: {err}", path.display()),
+            Self::Invalid(line, reason) => write!(f, "line {line} of the class list: {reason}"),
+            Self::Db(err) => write!(f, "could not import the class list: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ImportError};
+
+    fn rows(content: &str) -> Vec<Vec<String>> {
+        parse(content).unwrap()
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        assert_eq!(rows("\u{feff}name\nJane\n"), [vec!["name"], vec!["Jane"]]);
+    }
+
+    #[test]
+    fn quoted_separators() {
+        assert_eq!(
+            rows("name,class\n\"Doe, Jane\",3B\n"),
+            [vec!["name", "class"], vec!["Doe, Jane", "3B"]]
+        );
+    }
+
+    #[test]
+    fn doubled_quotes() {
+        assert_eq!(
+            rows("name,notes\nJane,\"said \"\"hi\"\"\"\nJoe,\"\"\n"),
+            [
+                vec!["name", "notes"],
+                vec!["Jane", "said \"hi\""],
+                vec!["Joe", ""]
+            ]
+        );
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!(
+            rows("name,class\r\nJane,3B\r\n\"Joe\r\nDoe\",3C\r\n"),
+            [
+                vec!["name", "class"],
+                vec!["Jane", "3B"],
+                vec!["Joe\r\nDoe", "3C"]
+            ]
+        );
+    }
+
+    #[test]
+    fn semicolons() {
+        assert_eq!(
+            rows("name;class;notes\nDoe, Jane;3B;x\n"),
+            [vec!["name", "class", "notes"], vec!["Doe, Jane", "3B", "x"]]
+        );
+    }
+
+    #[test]
+    fn no_trailing_newline() {
+        assert_eq!(
+            rows("name\n\nJane\n\nJoe"),
+            [vec!["name"], vec!["Jane"], vec!["Joe"]]
+        );
+    }
+
+    #[test]
+    fn unclosed_quote() {
+        assert!(matches!(
+            parse("name\nJane\n\"Joe\nDoe\n"),
+            Err(ImportError::Invalid(5, _))
+        ));
+    }
+}
@@ -6,9 +6,15 @@
 )]
 
 pub mod app;
+pub mod config;
 pub(crate) mod entities;
+pub mod error_log;
 pub mod errors;
+pub mod import;
+mod listener;
+pub mod migrate;
 pub mod popups;
+pub mod state;
 mod style;
 pub mod ui;
 mod view_data;
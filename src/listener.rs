@@ -0,0 +1,159 @@
+//! Live updates through postgres' LISTEN/NOTIFY
+//!
+//! The triggers added by the `notify_changes` migration send
+//! `<table> <operation> <id>` on the `verneanbud` channel whenever a row changes. The
+//! changed rows are fetched and handed to the ui thread, which applies them to
+//! the [`ViewData`](crate::view_data::ViewData)
+
+/// The state of the listener, shown in the status bar
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub enum Status {
+    /// The first connection hasn't been made yet
+    Connecting,
+    /// Changes made by others show up as they happen
+    Connected,
+    /// The connection was lost, and is being retried
+    Disconnected(String),
+}
+
+#[cfg(feature = "postgres")]
+pub(crate) use postgres::listen;
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::{sync::mpsc::Sender, time::Duration};
+
+    use async_std::task;
+    use sea_orm::{sqlx::postgres::PgListener, DatabaseConnection, DbErr, EntityTrait};
+
+    use super::Status;
+    use crate::{
+        app::Message,
+        entities::prelude::{
+            Comment as eComment, Idea as eIdea, Person as ePerson, Roster as eRoster,
+        },
+        view_data::{
+            idea::{Change, Idea},
+            meeting::Meetings,
+            roster::Roster,
+            ViewData,
+        },
+    };
+
+    /// The channel the triggers notify on
+    const CHANNEL: &str = "verneanbud";
+    /// How long to wait before trying to reconnect
+    const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+    /// Listens for changes until the receiving end of `sender` is dropped.
+    /// `db` has to be a postgres connection
+    pub(crate) async fn listen(db: DatabaseConnection, sender: Sender<Message>) {
+        let mut missed_changes = false;
+        loop {
+            let err = match listen_once(&db, &sender, missed_changes).await {
+                Ok(()) => return,
+                Err(err) => err,
+            };
+            if sender
+                .send(Message::Listener(Status::Disconnected(err)))
+                .is_err()
+            {
+                return;
+            }
+            missed_changes = true;
+            task::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Connects and forwards changes until something goes wrong. `Ok` means
+    /// the app is gone
+    async fn listen_once(
+        db: &DatabaseConnection,
+        sender: &Sender<Message>,
+        missed_changes: bool,
+    ) -> Result<(), String> {
+        let mut listener = PgListener::connect_with(db.get_postgres_connection_pool())
+            .await
+            .map_err(|err| err.to_string())?;
+        listener
+            .listen(CHANNEL)
+            .await
+            .map_err(|err| err.to_string())?;
+        if sender.send(Message::Listener(Status::Connected)).is_err() {
+            return Ok(());
+        }
+
+        // Anything could have happened while we weren't listening
+        if missed_changes
+            && sender
+                .send(Message::Refreshed(ViewData::fetch(db).await))
+                .is_err()
+        {
+            return Ok(());
+        }
+
+        loop {
+            let notification = listener
+                .try_recv()
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or("the connection was lost")?;
+            let Some(change) = fetch(db, notification.payload())
+                .await
+                .map_err(|err| err.to_string())?
+            else {
+                continue;
+            };
+            if sender.send(Message::Changed(change)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Fetches the row described by `payload`. Payloads for tables we don't
+    /// care about are ignored
+    async fn fetch(db: &DatabaseConnection, payload: &str) -> Result<Option<Change>, DbErr> {
+        let mut parts = payload.split_whitespace();
+        let (Some(table), Some(operation), Some(Ok(id))) =
+            (parts.next(), parts.next(), parts.next().map(str::parse))
+        else {
+            return Ok(None);
+        };
+        let deleted = operation == "DELETE";
+
+        Ok(Some(match table {
+            "idea" if deleted => Change::IdeaDeleted(id),
+            "idea" => eIdea::find_by_id(id)
+                .one(db)
+                .await?
+                .map_or(Change::IdeaDeleted(id), Change::Idea),
+            "comment" if deleted => Change::CommentDeleted(id),
+            "comment" => eComment::find_by_id(id)
+                .one(db)
+                .await?
+                .map_or(Change::CommentDeleted(id), Change::Comment),
+            // The id is the idea's, see the tags migration
+            "idea_tag" => Change::Tags(id, Idea::fetch_tags(db, id).await?),
+            "vote" => Change::Votes(id, Idea::fetch_votes(db, id).await?),
+            "assignee" => Change::Assignees(id, Idea::fetch_assignees(db, id).await?),
+            "person" if deleted => Change::PersonDeleted(id),
+            "person" => ePerson::find_by_id(id)
+                .one(db)
+                .await?
+                .map_or(Change::PersonDeleted(id), Change::Person),
+            // Both are keyed by the person, see the roster migration
+            "roster" if deleted => Change::RosterDeleted(id),
+            "roster" => eRoster::find_by_id(id)
+                .one(db)
+                .await?
+                .map_or(Change::RosterDeleted(id), Change::Roster),
+            "roster_idea" => Change::RosterIdeas(id, Roster::fetch_ideas(db, id).await?),
+            // All three are keyed by the meeting, see the meetings migration
+            "meeting" | "meeting_attendee" | "agenda_item" => Meetings::fetch_one(db, id)
+                .await?
+                .map_or(Change::MeetingDeleted(id), Change::Meeting),
+            _ => return Ok(None),
+        }))
+    }
+}
@@ -2,8 +2,13 @@
 //!
 //! This is a simple app used for storing ideas in an easily accessible way
 use futures::executor::block_on;
-use std::io;
-use verneanbud::{app::App, errors, ui::ui};
+use std::{env, io, time::Duration};
+use verneanbud::{
+    app::App,
+    config::{Args, Config, USAGE},
+    errors, import, migrate, state,
+    ui::ui,
+};
 
 use crossterm::{
     event::{
@@ -14,20 +19,56 @@ use crossterm::{
 };
 
 use ratatui::{backend::CrosstermBackend, Terminal};
+use sea_orm::{sqlx::types::chrono, Database};
 
 type Backend = CrosstermBackend<io::Stdout>;
 
+/// How long to wait for input before checking on the database actions
+/// running in the background
+const TICK_RATE: Duration = Duration::from_millis(50);
+
 fn main() -> color_eyre::Result<()> {
+    let args = Args::parse(env::args())?;
+    if args.help {
+        print!("{USAGE}");
+        return Ok(());
+    }
+    let config = Config::load(&args)?;
+    let db = block_on(Database::connect(config.connect_options()))?;
+    block_on(migrate::run(&db, &config))?;
+    block_on(App::empty_trash(&db, config.trash_retention_days))?;
+    if let Some(path) = args.import_roster {
+        let imported = block_on(import::roster(&db, &path))?;
+        println!(
+            "Put {} people on the roster, and updated the class of {} who were on it already",
+            imported.added, imported.updated
+        );
+        return Ok(());
+    }
+
     let mut terminal = setup_terminal()?;
     // create app and run it
-    let mut app = block_on(App::new())?;
+    let mut app = block_on(App::new(db, config.user.clone()))?;
+    if let Some(last_session) = state::last_session(&config.database_url) {
+        app.show_overdue_since(last_session);
+    }
     let res = run_app(&mut terminal, &mut app);
 
     restore_terminal(&mut terminal)?;
-
-    if let Ok(_do_print) = res {
-    } else if let Err(err) = res {
-        println!("{err:?}");
+    let interrupted = match res {
+        Ok(exit) => matches!(exit, Exit::Interrupted),
+        Err(err) => {
+            println!("{err:?}");
+            false
+        }
+    };
+    // It only tells what became overdue since, which isn't worth failing for
+    if let Err(err) = state::save_session(&config.database_url, chrono::Local::now().naive_local())
+    {
+        println!("Warning: the end of the session isn't remembered, {err}");
+    }
+    if interrupted {
+        std::process::exit(130);
     }
 
     Ok(())
@@ -54,10 +95,21 @@ fn restore_terminal(terminal: &mut Terminal<Backend>) -> color_eyre::Result<()>
     Ok(())
 }
 
-fn run_app(terminal: &mut Terminal<Backend>, app: &mut App) -> color_eyre::Result<()> {
+/// How the app was left
+enum Exit {
+    Quit,
+    /// With Ctrl-C
+    Interrupted,
+}
+
+fn run_app(terminal: &mut Terminal<Backend>, app: &mut App) -> color_eyre::Result<Exit> {
     loop {
         terminal.draw(|f| ui(f, app))?;
+        app.run_db_actions();
 
+        if !event::poll(TICK_RATE)? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
             if key.kind == event::KeyEventKind::Release {
                 continue;
@@ -70,13 +122,11 @@ fn run_app(terminal: &mut Terminal<Backend>, app: &mut App) -> color_eyre::Resul
                     ..
                 }
             ) {
-                restore_terminal(terminal)?;
-                std::process::exit(130);
+                return Ok(Exit::Interrupted);
             }
             if app.handle_input(key) {
-                return Ok(());
+                return Ok(Exit::Quit);
             };
-            app.run_db_actions()?;
         }
     }
 }
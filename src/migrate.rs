@@ -0,0 +1,61 @@
+//! Keeps the database schema in sync with the binary by running the
+//! migrations from the `migration` crate on startup
+use core::fmt;
+
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{DatabaseConnection, DbErr};
+
+use crate::config::Config;
+
+/// Applies all pending migrations. If `config.migrate` is false this instead
+/// fails when there are pending migrations, leaving the database untouched
+pub async fn run(db: &DatabaseConnection, config: &Config) -> Result<(), MigrateError> {
+    let pending = Migrator::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|x| x.name().to_string())
+        .collect::<Vec<_>>();
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+    if !config.migrate {
+        return Err(MigrateError::Pending(pending));
+    }
+
+    println!("Applying {} pending migration(s)", pending.len());
+    Migrator::up(db, None).await?;
+    Ok(())
+}
+
+/// Why the schema couldn't be brought up to date
+#[derive(Debug)]
+pub enum MigrateError {
+    /// Running a migration failed
+    Db(DbErr),
+    /// There are pending migrations, but `--no-migrate` was passed
+    Pending(Vec<String>),
+}
+
+impl From<DbErr> for MigrateError {
+    fn from(value: DbErr) -> Self {
+        Self::Db(value)
+    }
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Db(err) => write!(f, "could not migrate the database: {err}"),
+            Self::Pending(pending) => write!(
+                f,
+                "the database is missing {} migration(s): {}\n\
+                 Start without --no-migrate to apply them",
+                pending.len(),
+                pending.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MigrateError {}
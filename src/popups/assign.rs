@@ -0,0 +1,128 @@
+//! The popup that appears when you want to choose who follows up an idea
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use futures::FutureExt;
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use crate::{
+    app::DbActionParts,
+    popups::Popup,
+    style::Style,
+    view_data::{
+        idea::{save_assignees, IdeaEntry},
+        ViewData,
+    },
+};
+
+use super::{tags::TagInput, Action};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct AssignPopup {
+    assignees: TagInput,
+    id: i32,
+}
+
+impl Popup for AssignPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(1)])
+            .split(area);
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(Clear, layout[1]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title("Assigned to (\u{2192} to complete)");
+        self.assignees.render(block, layout[0], frame);
+        frame.render_widget(
+            Paragraph::new("Separate people with spaces, Enter to save"),
+            layout[1],
+        );
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.assignees.text = self
+                    .assignees
+                    .text
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Right => {
+                    self.assignees.complete();
+                }
+                KeyCode::Backspace => {
+                    self.assignees.text.pop();
+                }
+                KeyCode::Char(c) => self.assignees.text.push(c),
+                KeyCode::Enter => {
+                    // Accept what is being suggested, like in the other forms
+                    if self.assignees.complete() {
+                        return Action::Nothing;
+                    }
+                    let assignees = self.assignees.tags();
+                    let id = self.id;
+                    return Action::Db(Box::new(
+                        move |view_data: &mut ViewData, db: DatabaseConnection| {
+                            let (action_id, previous) = view_data.idea.edit_idea(id, |_| ())?;
+                            let previous_assignees =
+                                view_data.idea.set_assignees(id, assignees.clone())?;
+                            Some((
+                                action_id,
+                                DbActionParts {
+                                    description: format!("Assigning the idea {:?}", previous.title),
+                                    action: Box::new(move || {
+                                        let db = db.clone();
+                                        let assignees = assignees.clone();
+                                        async move {
+                                            let txn = db.begin().await?;
+                                            save_assignees(&txn, id, &assignees).await?;
+                                            txn.commit().await?;
+                                            Ok(None)
+                                        }
+                                        .boxed()
+                                    }),
+                                    callback: Box::new(move |view_data: &mut ViewData, _| {
+                                        let _ = view_data.idea.completed(action_id, |_| ());
+                                    }),
+                                    rollback: Box::new(move |view_data: &mut ViewData| {
+                                        view_data.idea.set_assignees(id, previous_assignees);
+                                        view_data.idea.rollback(action_id, Some(previous));
+                                    }),
+                                },
+                            ))
+                        },
+                    ));
+                }
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl AssignPopup {
+    /// Starts out with who is assigned to `idea`, completing the `known`
+    /// people
+    pub fn new(idea: &IdeaEntry, known: Vec<String>) -> Self {
+        Self {
+            assignees: TagInput::new(known, &idea.assignees),
+            id: idea.idea.get_entry().id,
+        }
+    }
+}
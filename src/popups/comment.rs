@@ -1,4 +1,4 @@
-//! The popup that appears when you want to insert a new idea into the db
+//! The popup that appears when you want to write a comment, or change one
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use futures::FutureExt;
 use ratatui::{
@@ -6,21 +6,33 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use sea_orm::{sqlx::types::chrono, ActiveValue, ConnectOptions, Database, EntityTrait};
+use sea_orm::{
+    sqlx::types::chrono, ActiveValue, DatabaseConnection, EntityTrait, TransactionTrait,
+};
 
 use crate::{
-    entities::{comment, prelude::Comment},
+    app::DbActionParts,
+    entities::{
+        comment, comment_revision,
+        prelude::{Comment, CommentRevision},
+    },
     popups::Popup,
     style::Style,
-    view_data::ViewData,
+    view_data::{people::find_or_create, ViewData},
 };
 
-use super::Action;
+use super::{person::PersonInput, Action};
 
 #[derive(Default, Clone, Debug)]
 pub(crate) struct CommontPopup {
-    pub(crate) author: String,
+    author: PersonInput,
     pub(crate) content: String,
+    /// The comment that is replied to, if any
+    parent: Option<i32>,
+    /// The comment that is changed. `None` for a new one
+    id: Option<i32>,
+    /// Who is editing, recorded with the version that is replaced
+    editor: String,
     selected: Selected,
 }
 
@@ -42,11 +54,8 @@ impl Popup for CommontPopup {
             } else {
                 style.not_highlighted
             })
-            .title("Author");
-        let para = Paragraph::new(self.author.clone())
-            .block(block)
-            .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[0]);
+            .title("Author (\u{2192} to complete)");
+        self.author.render(block, layout[0], frame);
 
         let block = base_block
             .clone()
@@ -55,7 +64,11 @@ impl Popup for CommontPopup {
             } else {
                 style.not_highlighted
             })
-            .title("Content");
+            .title(if self.parent.is_some() {
+                "Reply"
+            } else {
+                "Content"
+            });
         let para = Paragraph::new(self.content.clone())
             .block(block)
             .wrap(Wrap { trim: false });
@@ -79,57 +92,20 @@ impl Popup for CommontPopup {
                 KeyCode::Esc => return Action::Close,
                 KeyCode::Tab => self.selected = self.selected.next(),
                 KeyCode::BackTab => self.selected = self.selected.prev(),
+                KeyCode::Right if matches!(self.selected, Selected::Author) => {
+                    self.author.complete();
+                }
                 KeyCode::Backspace => drop(self.get_str_handle().pop()),
                 KeyCode::Char(c) => self.get_str_handle().push(c),
                 KeyCode::Enter if matches!(self.selected, Selected::Author) => {
-                    let cloned = self.clone();
-                    return Action::Db(Box::new(
-                        move |view_data: &mut ViewData, conn_opts: ConnectOptions| {
-                            let to_insert = comment::Model {
-                                id: -1,
-                                author: cloned.author.clone(),
-                                content: cloned.content,
-                                time: chrono::Local::now().naive_local(),
-                                comments_on: view_data.idea.current().unwrap().0.get_entry().id,
-                            };
-
-                            let id = view_data
-                                .idea
-                                .new_comment(view_data.idea.selected.unwrap(), to_insert.clone());
-
-                            let to_insert_active_model = comment::ActiveModel {
-                                author: ActiveValue::Set(to_insert.author.clone()),
-                                content: ActiveValue::Set(to_insert.content.clone()),
-                                time: ActiveValue::Set(to_insert.time),
-                                comments_on: ActiveValue::Set(to_insert.comments_on),
-                                ..Default::default()
-                            };
-                            Some((
-                                id,
-                                (
-                                    async move {
-                                        let db = Database::connect(conn_opts).await?;
-
-                                        let a = Comment::insert(to_insert_active_model)
-                                            .exec(&db)
-                                            .await?;
-
-                                        Ok(Some(a.last_insert_id))
-                                    }
-                                    .boxed(),
-                                    Box::new(
-                                        move |view_data: &mut ViewData, new_id: Option<i32>| {
-                                            let _ = view_data.idea.completed(id, |x| {
-                                                x.0.get_entry_mut().id = new_id.expect(
-                                                    "This method cannot be called with None",
-                                                );
-                                            });
-                                        },
-                                    ),
-                                ),
-                            ))
-                        },
-                    ));
+                    let name = self.author.name();
+                    if name.is_empty() {
+                        return Action::Nothing;
+                    }
+                    return match self.id {
+                        Some(id) => self.update(id, name),
+                        None => self.insert(name),
+                    };
                 }
                 KeyCode::Enter => self.get_str_handle().push('\n'),
                 _ => (),
@@ -140,9 +116,165 @@ impl Popup for CommontPopup {
 }
 
 impl CommontPopup {
+    /// An empty form, completing the `people` that exist
+    pub fn new(people: Vec<String>) -> Self {
+        Self {
+            author: PersonInput::new(people, ""),
+            ..Default::default()
+        }
+    }
+
+    /// A form for replying to the comment with the id
+    pub fn reply_to(parent: i32, people: Vec<String>) -> Self {
+        Self {
+            parent: Some(parent),
+            ..Self::new(people)
+        }
+    }
+
+    /// A form filled with `previous`, which was written by `author`, to
+    /// change it. The edit is recorded as made by `editor`
+    pub fn edit(
+        previous: &comment::Model,
+        author: &str,
+        people: Vec<String>,
+        editor: String,
+    ) -> Self {
+        Self {
+            author: PersonInput::new(people, author),
+            content: previous.content.clone(),
+            id: Some(previous.id),
+            editor,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the comment, written by `name`
+    fn insert<'a>(&self, name: String) -> Action<'a> {
+        let cloned = self.clone();
+        Action::Db(Box::new(
+            move |view_data: &mut ViewData, db: DatabaseConnection| {
+                let to_insert = comment::Model {
+                    id: -1,
+                    author_id: view_data.people.id_or_insert(&name),
+                    content: cloned.content,
+                    time: chrono::Local::now().naive_local(),
+                    comments_on: view_data.idea.current()?.idea.get_entry().id,
+                    parent_id: cloned.parent,
+                    edited_at: None,
+                };
+
+                let id = view_data.idea.new_comment(to_insert.clone())?;
+
+                let to_insert_active_model = comment::ActiveModel {
+                    content: ActiveValue::Set(to_insert.content.clone()),
+                    time: ActiveValue::Set(to_insert.time),
+                    comments_on: ActiveValue::Set(to_insert.comments_on),
+                    parent_id: ActiveValue::Set(to_insert.parent_id),
+                    ..Default::default()
+                };
+                Some((
+                    id,
+                    DbActionParts {
+                        description: if cloned.parent.is_some() {
+                            "Replying to a comment".to_string()
+                        } else {
+                            "Adding a comment".to_string()
+                        },
+                        action: Box::new(move || {
+                            let db = db.clone();
+                            let mut to_insert_active_model = to_insert_active_model.clone();
+                            let name = name.clone();
+                            async move {
+                                let txn = db.begin().await?;
+                                to_insert_active_model.author_id =
+                                    ActiveValue::Set(find_or_create(&txn, &name).await?);
+                                let a = Comment::insert(to_insert_active_model).exec(&txn).await?;
+                                txn.commit().await?;
+
+                                Ok(Some(a.last_insert_id))
+                            }
+                            .boxed()
+                        }),
+                        callback: Box::new(move |view_data: &mut ViewData, new_id: Option<i32>| {
+                            let _ = view_data.idea.completed_comment(id, |x| {
+                                x.id = new_id.expect("This method cannot be called with None");
+                            });
+                        }),
+                        rollback: Box::new(move |view_data: &mut ViewData| {
+                            view_data.idea.rollback_comment(id, None);
+                        }),
+                    },
+                ))
+            },
+        ))
+    }
+
+    /// Replaces the comment with the id by what's entered, now written by
+    /// `name`
+    fn update<'a>(&self, id: i32, name: String) -> Action<'a> {
+        let cloned = self.clone();
+        let now = chrono::Local::now().naive_local();
+        Action::Db(Box::new(
+            move |view_data: &mut ViewData, db: DatabaseConnection| {
+                let author_id = view_data.people.id_or_insert(&name);
+                let (action_id, previous) = view_data.idea.edit_comment(id, |comment| {
+                    comment.author_id = author_id;
+                    comment.content.clone_from(&cloned.content);
+                    comment.edited_at = Some(now);
+                })?;
+
+                let previous_author = view_data.people.name(previous.author_id).to_string();
+                let to_update = comment::ActiveModel {
+                    content: ActiveValue::Set(cloned.content),
+                    edited_at: ActiveValue::Set(Some(now)),
+                    id: ActiveValue::Unchanged(id),
+                    ..Default::default()
+                };
+                let revision = comment_revision::ActiveModel {
+                    comment: ActiveValue::Set(id),
+                    author: ActiveValue::Set(previous_author.clone()),
+                    content: ActiveValue::Set(previous.content.clone()),
+                    editor: ActiveValue::Set(cloned.editor),
+                    time: ActiveValue::Set(now),
+                    ..Default::default()
+                };
+                Some((
+                    action_id,
+                    DbActionParts {
+                        description: format!("Editing the comment by {previous_author}"),
+                        action: Box::new(move || {
+                            let db = db.clone();
+                            let mut to_update = to_update.clone();
+                            let revision = revision.clone();
+                            let name = name.clone();
+                            async move {
+                                let txn = db.begin().await?;
+                                to_update.author_id =
+                                    ActiveValue::Set(find_or_create(&txn, &name).await?);
+                                CommentRevision::insert(revision).exec(&txn).await?;
+                                Comment::update(to_update).exec(&txn).await?;
+                                txn.commit().await?;
+                                Ok(None)
+                            }
+                            .boxed()
+                        }),
+                        callback: Box::new(move |view_data: &mut ViewData, new_id: Option<i32>| {
+                            assert!(new_id.is_none());
+                            let _ = view_data.idea.completed_comment(action_id, |_| ());
+                        }),
+                        rollback: Box::new(move |view_data: &mut ViewData| {
+                            view_data.idea.rollback_comment(action_id, Some(previous));
+                        }),
+                    },
+                ))
+            },
+        ))
+    }
+
     fn get_str_handle(&mut self) -> &mut String {
         match self.selected {
-            Selected::Author => &mut self.author,
+            Selected::Author => &mut self.author.text,
             Selected::Content => &mut self.content,
         }
     }
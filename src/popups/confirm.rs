@@ -0,0 +1,79 @@
+//! The popup asking whether something should really be done, for actions
+//! that can't be taken back
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    popups::Popup,
+    style::Style,
+    view_data::{idea::Idea, meeting::Meetings},
+};
+
+use super::Action;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ConfirmPopup {
+    question: String,
+    /// What is done once the user agrees
+    action: Confirmed,
+}
+
+/// The actions that are confirmed before they are taken
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Confirmed {
+    /// Deleting the comment with the id
+    DeleteComment(i32),
+    /// Moving the idea with the id to the trash
+    DeleteIdea(i32),
+    /// Deleting the idea with the id for good
+    PurgeIdea(i32),
+    /// Deleting the meeting with the id, with its agenda
+    DeleteMeeting(i32),
+}
+
+impl Popup for ConfirmPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Fill(1)])
+            .split(area);
+        frame.render_widget(Clear, layout[0]);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title("Are you sure?");
+        let para = Paragraph::new(vec![
+            Line::raw(self.question.clone()),
+            Line::raw("y: yes, n: no"),
+        ])
+        .block(block)
+        .wrap(Wrap { trim: false });
+        frame.render_widget(para, layout[0]);
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key.code {
+            KeyCode::Char('y') => Action::Db(match self.action {
+                Confirmed::DeleteComment(id) => Idea::delete_comment(id),
+                Confirmed::DeleteIdea(id) => Idea::delete(id),
+                Confirmed::PurgeIdea(id) => Idea::purge(id),
+                Confirmed::DeleteMeeting(id) => Meetings::delete(id),
+            }),
+            KeyCode::Char('n') | KeyCode::Esc => Action::Close,
+            _ => Action::Nothing,
+        }
+    }
+}
+
+impl ConfirmPopup {
+    /// Asks `question`, taking `action` if the answer is yes
+    pub const fn new(question: String, action: Confirmed) -> Self {
+        Self { question, action }
+    }
+}
@@ -0,0 +1,112 @@
+//! The field used for entering a date, optionally with a time of day
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+use chrono::{NaiveDate, TimeDelta};
+use sea_orm::prelude::DateTime;
+
+/// How dates are entered
+const DATE_FORMAT: &str = "%d/%m/%Y";
+/// How dates with a time of day are entered
+const TIME_FORMAT: &str = "%d/%m/%Y %H:%M";
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct DateInput {
+    /// The date as typed
+    pub(crate) text: String,
+    /// Whether a time of day is entered after the date
+    with_time: bool,
+}
+
+impl DateInput {
+    /// Starts out with `time`, or empty if there is none
+    pub fn new(time: Option<DateTime>, with_time: bool) -> Self {
+        let mut ret = Self {
+            text: String::new(),
+            with_time,
+        };
+        if let Some(time) = time {
+            ret.set(time);
+        }
+        ret
+    }
+
+    /// The entered date. `None` if nothing is entered, `Err` if it isn't a
+    /// date. A date without a time means the start of the day
+    pub fn value(&self) -> Result<Option<DateTime>, ()> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        DateTime::parse_from_str(text, TIME_FORMAT)
+            .or_else(|_| {
+                NaiveDate::parse_from_str(text, DATE_FORMAT).map(|x| x.and_time(Default::default()))
+            })
+            .map(Some)
+            .map_err(|_| ())
+    }
+
+    /// Moves the date a day ahead, or back. An empty field starts out at
+    /// today, or the next full hour if a time is entered too. A date at the
+    /// end of what can be entered stays as it is
+    pub fn step(&mut self, forward: bool) {
+        match self.value() {
+            Ok(Some(time)) => {
+                let day = TimeDelta::days(1);
+                let stepped = if forward {
+                    time.checked_add_signed(day)
+                } else {
+                    time.checked_sub_signed(day)
+                };
+                if let Some(time) = stepped {
+                    self.set(time);
+                }
+            }
+            Ok(None) => {
+                let now = chrono::Local::now().naive_local();
+                let today = now.date().and_time(Default::default());
+                self.set(if self.with_time {
+                    let hours = u64::try_from((now - today).num_hours()).unwrap_or(0);
+                    today + Duration::from_secs((hours + 1) * 60 * 60)
+                } else {
+                    today
+                });
+            }
+            Err(()) => (),
+        }
+    }
+
+    fn set(&mut self, time: DateTime) {
+        self.text = time
+            .format(if self.with_time {
+                TIME_FORMAT
+            } else {
+                DATE_FORMAT
+            })
+            .to_string();
+    }
+
+    pub fn render(&self, block: Block, area: Rect, frame: &mut Frame) {
+        let line = if self.text.is_empty() {
+            Line::styled(
+                if self.with_time {
+                    "dd/mm/yyyy hh:mm"
+                } else {
+                    "dd/mm/yyyy"
+                },
+                Style::new().dark_gray(),
+            )
+        } else if self.value().is_err() {
+            Line::from(Span::styled(self.text.clone(), Style::new().red()))
+        } else {
+            Line::raw(self.text.clone())
+        };
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+}
@@ -0,0 +1,84 @@
+//! The popup that appears when you want to write down what a meeting decided
+//! about an idea on its agenda
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{entities::agenda_item, popups::Popup, style::Style, view_data::meeting::Meetings};
+
+use super::Action;
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct DecisionPopup {
+    meeting: i32,
+    idea: i32,
+    title: String,
+    pub(crate) decision: String,
+}
+
+impl Popup for DecisionPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(8), Constraint::Length(1)])
+            .split(area);
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(Clear, layout[1]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title(format!("Decision on {}", self.title));
+        let para = Paragraph::new(self.decision.clone())
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(para, layout[0]);
+        frame.render_widget(Paragraph::new("Enter to save"), layout[1]);
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.decision = self
+                    .decision
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Backspace => drop(self.decision.pop()),
+                KeyCode::Char(c) => self.decision.push(c),
+                KeyCode::Enter => {
+                    return Action::Db(Meetings::set_decision(
+                        self.meeting,
+                        self.idea,
+                        self.decision.trim().to_string(),
+                    ));
+                }
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl DecisionPopup {
+    /// A form for what was decided about `item`, the idea called `title`
+    pub fn new(item: &agenda_item::Model, title: &str) -> Self {
+        Self {
+            meeting: item.meeting,
+            idea: item.idea,
+            title: title.to_string(),
+            decision: item.decision.clone(),
+        }
+    }
+}
@@ -7,26 +7,43 @@ use ratatui::{
     Frame,
 };
 use sea_orm::{
-    sqlx::types::chrono, ActiveValue, ColumnTrait, ConnectOptions, Database, EntityTrait,
-    QueryFilter,
+    sqlx::types::chrono, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait,
 };
 
 use crate::{
-    entities::{idea, prelude::Idea, sea_orm_active_enums::Issuekind},
+    app::DbActionParts,
+    entities::{
+        idea, idea_revision,
+        prelude::{Idea, IdeaRevision},
+        sea_orm_active_enums::Priority,
+    },
     popups::Popup,
     style::Style,
-    view_data::{idea::IdeaType, ViewData},
+    view_data::{
+        idea::{save_tags, IdeaEntry},
+        people::find_or_create,
+        ViewData,
+    },
 };
 
-use super::Action;
+use super::{date::DateInput, kind::KindSelector, person::PersonInput, tags::TagInput, Action};
 
 #[derive(Default, Clone, Debug)]
 pub(crate) struct EditPopup {
-    pub(crate) author: String,
+    author: PersonInput,
     pub(crate) title: String,
     pub(crate) description: String,
+    /// Anonymous ideas keep their author hidden, so it can't be edited
+    anonymous: bool,
+    kind: KindSelector,
+    priority: Priority,
+    due: DateInput,
+    tags: TagInput,
     selected: Selected,
     id: i32,
+    /// Who is editing, recorded with the version that is replaced
+    editor: String,
 }
 
 impl Popup for EditPopup {
@@ -35,6 +52,10 @@ impl Popup for EditPopup {
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Fill(1),
@@ -51,12 +72,12 @@ impl Popup for EditPopup {
             } else {
                 style.not_highlighted
             })
-            .title("Author");
-
-        let para = Paragraph::new(self.author.clone())
-            .block(block)
-            .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[0]);
+            .title(if self.anonymous {
+                "Author (submitted anonymously)"
+            } else {
+                "Author (\u{2192} to complete)"
+            });
+        self.author.render(block, layout[0], frame);
 
         let block = base_block
             .clone()
@@ -71,6 +92,48 @@ impl Popup for EditPopup {
             .wrap(Wrap { trim: false });
         frame.render_widget(para, layout[1]);
 
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Kind) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Kind");
+        self.kind.render(block, layout[2], frame);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Priority) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Priority");
+        let para =
+            Paragraph::new(format!("\u{2190} {} \u{2192}", self.priority.name())).block(block);
+        frame.render_widget(para, layout[3]);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Due) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Follow up by (\u{2191}/\u{2193} to change the day, can be left empty)");
+        self.due.render(block, layout[4], frame);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Tags) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Tags (\u{2192} to complete)");
+        self.tags.render(block, layout[5], frame);
+
         let block = base_block
             .clone()
             .border_style(if matches!(self.selected, Selected::Description) {
@@ -82,7 +145,7 @@ impl Popup for EditPopup {
         let para = Paragraph::new(self.description.clone())
             .block(block)
             .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[2]);
+        frame.render_widget(para, layout[6]);
     }
 
     fn handle_input<'a>(&mut self, key: &crossterm::event::KeyEvent) -> Action<'a> {
@@ -92,74 +155,153 @@ impl Popup for EditPopup {
                 modifiers: KeyModifiers::CONTROL,
                 ..
             } => {
-                *self.get_str_handle() = self
-                    .get_str_handle()
-                    .rsplit_once(' ')
-                    .map_or(String::new(), |x| x.0.to_string());
+                if let Some(handle) = self.get_str_handle() {
+                    *handle = handle
+                        .rsplit_once(' ')
+                        .map_or(String::new(), |x| x.0.to_string());
+                }
             }
 
             x => match x.code {
                 KeyCode::Esc => return Action::Close,
                 KeyCode::Tab => self.selected = self.selected.next(),
                 KeyCode::BackTab => self.selected = self.selected.prev(),
-                KeyCode::Backspace => drop(self.get_str_handle().pop()),
-                KeyCode::Char(c) => self.get_str_handle().push(c),
+                KeyCode::Left if matches!(self.selected, Selected::Kind) => self.kind.prev(),
+                KeyCode::Right if matches!(self.selected, Selected::Kind) => self.kind.next(),
+                KeyCode::Left if matches!(self.selected, Selected::Priority) => {
+                    self.priority = self.priority.prev();
+                }
+                KeyCode::Right if matches!(self.selected, Selected::Priority) => {
+                    self.priority = self.priority.next();
+                }
+                KeyCode::Up if matches!(self.selected, Selected::Due) => self.due.step(true),
+                KeyCode::Down if matches!(self.selected, Selected::Due) => self.due.step(false),
+                KeyCode::Right if matches!(self.selected, Selected::Author) && !self.anonymous => {
+                    self.author.complete();
+                }
+                KeyCode::Right | KeyCode::Enter if matches!(self.selected, Selected::Tags) => {
+                    self.tags.complete();
+                }
+                KeyCode::Backspace => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.push(c);
+                    }
+                }
                 KeyCode::Enter if matches!(self.selected, Selected::Title) => {
-                    let kind = Issuekind::Issue;
+                    let Some(kind) = self.kind.current().map(str::to_string) else {
+                        return Action::Nothing;
+                    };
+                    let name = self.author.name();
+                    if name.is_empty() && !self.anonymous {
+                        return Action::Nothing;
+                    }
+                    let Ok(due) = self.due.value() else {
+                        self.selected = Selected::Due;
+                        return Action::Nothing;
+                    };
+                    let anonymous = self.anonymous;
                     let cloned = self.clone();
+                    let priority = self.priority;
                     let id = self.id;
+                    let now = chrono::Local::now().naive_local();
                     return Action::Db(Box::new(
-                        move |view_data: &mut ViewData, conn_opts: ConnectOptions| {
-                            let to_insert = idea::Model {
-                                id,
-                                title: cloned.title.clone(),
-                                description: cloned.description.clone(),
-                                author: cloned.author.clone(),
-                                solved: false,
-                                kind,
-                                time: chrono::Local::now().naive_local(),
-                            };
-                            let action_id = view_data.idea.edit_idea(&to_insert)?;
+                        move |view_data: &mut ViewData, db: DatabaseConnection| {
+                            let author_id =
+                                (!anonymous).then(|| view_data.people.id_or_insert(&name));
+                            let (action_id, previous) = view_data.idea.edit_idea(id, |entry| {
+                                if author_id.is_some() {
+                                    entry.author_id = author_id;
+                                }
+                                entry.title.clone_from(&cloned.title);
+                                entry.description.clone_from(&cloned.description);
+                                entry.kind.clone_from(&kind);
+                                entry.priority = priority;
+                                entry.due = due;
+                                entry.edited_at = Some(now);
+                            })?;
+                            let tags = cloned.tags.tags();
+                            let previous_tags = view_data.idea.set_tags(id, tags.clone())?;
+                            view_data.add_tags(&tags);
 
+                            // Only what can be edited here is written, so
+                            // the status isn't reset
                             let to_insert_active_model = idea::ActiveModel {
-                                title: ActiveValue::Set(to_insert.title.clone()),
-                                description: ActiveValue::Set(to_insert.description.clone()),
-                                author: ActiveValue::Set(to_insert.author.clone()),
-                                solved: ActiveValue::Set(to_insert.solved),
-                                kind: ActiveValue::Set(to_insert.kind),
-                                id: ActiveValue::Unchanged(to_insert.id),
+                                title: ActiveValue::Set(cloned.title.clone()),
+                                description: ActiveValue::Set(cloned.description.clone()),
+                                kind: ActiveValue::Set(kind),
+                                priority: ActiveValue::Set(priority),
+                                due: ActiveValue::Set(due),
+                                edited_at: ActiveValue::Set(Some(now)),
+                                id: ActiveValue::Unchanged(id),
+                                ..Default::default()
+                            };
+                            let revision = idea_revision::ActiveModel {
+                                idea: ActiveValue::Set(id),
+                                title: ActiveValue::Set(previous.title.clone()),
+                                description: ActiveValue::Set(previous.description.clone()),
+                                author: ActiveValue::Set(view_data.author(&previous).to_string()),
+                                kind: ActiveValue::Set(previous.kind.clone()),
+                                editor: ActiveValue::Set(cloned.editor.clone()),
+                                time: ActiveValue::Set(now),
                                 ..Default::default()
                             };
                             Some((
                                 action_id,
-                                (
-                                    async move {
-                                        let db = Database::connect(conn_opts).await?;
-
-                                        Idea::update(to_insert_active_model)
-                                            .filter(idea::Column::Id.eq(id))
-                                            .exec(&db)
-                                            .await?;
-                                        Ok(None)
-                                    }
-                                    .boxed(),
-                                    Box::new(
+                                DbActionParts {
+                                    description: format!("Editing the idea {:?}", cloned.title),
+                                    action: Box::new(move || {
+                                        let db = db.clone();
+                                        let mut to_insert_active_model =
+                                            to_insert_active_model.clone();
+                                        let revision = revision.clone();
+                                        let tags = tags.clone();
+                                        let name = name.clone();
+                                        async move {
+                                            let txn = db.begin().await?;
+                                            // Anonymous ideas keep their author hidden
+                                            if !anonymous {
+                                                to_insert_active_model.author_id = ActiveValue::Set(
+                                                    Some(find_or_create(&txn, &name).await?),
+                                                );
+                                            }
+                                            IdeaRevision::insert(revision).exec(&txn).await?;
+                                            Idea::update(to_insert_active_model)
+                                                .filter(idea::Column::Id.eq(id))
+                                                .exec(&txn)
+                                                .await?;
+                                            save_tags(&txn, id, &tags).await?;
+                                            txn.commit().await?;
+                                            Ok(None)
+                                        }
+                                        .boxed()
+                                    }),
+                                    callback: Box::new(
                                         move |view_data: &mut ViewData, new_id: Option<i32>| {
                                             assert!(new_id.is_none());
-                                            let _ = view_data.idea.completed(action_id, |x| {
-                                                let entry = x.0.get_entry_mut();
-                                                entry.author = to_insert.author;
-                                                entry.title = to_insert.title;
-                                                entry.description = to_insert.description;
-                                            });
+                                            let _ = view_data.idea.completed(action_id, |_| ());
                                         },
                                     ),
-                                ),
+                                    rollback: Box::new(move |view_data: &mut ViewData| {
+                                        view_data.idea.rollback(action_id, Some(previous));
+                                        view_data.idea.set_tags(id, previous_tags);
+                                    }),
+                                },
                             ))
                         },
                     ));
                 }
-                KeyCode::Enter => self.get_str_handle().push('\n'),
+                // Dates are a single line
+                KeyCode::Enter if matches!(self.selected, Selected::Due) => (),
+                KeyCode::Enter => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.push('\n');
+                    }
+                }
                 _ => (),
             },
         }
@@ -168,22 +310,43 @@ impl Popup for EditPopup {
 }
 
 impl EditPopup {
-    pub fn new(previous: &IdeaType) -> Self {
-        let entry = previous.0.get_entry();
+    /// A form filled with `previous`, which was submitted by `author`,
+    /// offering `kinds` to choose from and completing the `tags` and
+    /// `people` that exist. The edit is recorded as made by `editor`
+    pub fn new(
+        previous: &IdeaEntry,
+        author: &str,
+        kinds: Vec<String>,
+        tags: Vec<String>,
+        people: Vec<String>,
+        editor: String,
+    ) -> Self {
+        let entry = previous.idea.get_entry();
         Self {
-            author: entry.author.clone(),
+            author: PersonInput::new(people, author),
             title: entry.title.clone(),
             description: entry.description.clone(),
+            anonymous: entry.anonymous,
+            kind: KindSelector::new(kinds, Some(&entry.kind)),
+            priority: entry.priority,
+            due: DateInput::new(entry.due, false),
+            tags: TagInput::new(tags, &previous.tags),
             selected: Selected::Author,
             id: entry.id,
+            editor,
         }
     }
 
-    fn get_str_handle(&mut self) -> &mut String {
+    /// The text of the selected field. `None` if it isn't a text field
+    fn get_str_handle(&mut self) -> Option<&mut String> {
         match self.selected {
-            Selected::Author => &mut self.author,
-            Selected::Title => &mut self.title,
-            Selected::Description => &mut self.description,
+            Selected::Author if self.anonymous => None,
+            Selected::Author => Some(&mut self.author.text),
+            Selected::Title => Some(&mut self.title),
+            Selected::Kind | Selected::Priority => None,
+            Selected::Due => Some(&mut self.due.text),
+            Selected::Tags => Some(&mut self.tags.text),
+            Selected::Description => Some(&mut self.description),
         }
     }
 }
@@ -193,6 +356,10 @@ enum Selected {
     #[default]
     Author,
     Title,
+    Kind,
+    Priority,
+    Due,
+    Tags,
     Description,
 }
 
@@ -200,7 +367,11 @@ impl Selected {
     pub const fn next(&self) -> Self {
         match self {
             Self::Author => Self::Title,
-            Self::Title => Self::Description,
+            Self::Title => Self::Kind,
+            Self::Kind => Self::Priority,
+            Self::Priority => Self::Due,
+            Self::Due => Self::Tags,
+            Self::Tags => Self::Description,
             Self::Description => Self::Author,
         }
     }
@@ -209,7 +380,11 @@ impl Selected {
         match self {
             Self::Author => Self::Description,
             Self::Title => Self::Author,
-            Self::Description => Self::Title,
+            Self::Kind => Self::Title,
+            Self::Priority => Self::Kind,
+            Self::Due => Self::Priority,
+            Self::Tags => Self::Due,
+            Self::Description => Self::Tags,
         }
     }
 }
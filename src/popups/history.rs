@@ -0,0 +1,288 @@
+//! The popup showing how an idea or a comment changed over time
+use std::mem;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style as rataStyle},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+use sea_orm::prelude::DateTime;
+
+use crate::{
+    entities::{comment, comment_revision, idea, idea_revision, status_history},
+    popups::Popup,
+    style::Style,
+};
+
+use super::Action;
+
+#[derive(Clone, Debug)]
+pub(crate) struct HistoryPopup {
+    /// Every version of the idea or comment, oldest first
+    versions: Vec<Version>,
+    selected: usize,
+    /// Every change of the status of the idea, oldest first
+    statuses: Vec<status_history::Model>,
+}
+
+#[derive(Clone, Debug)]
+struct Version {
+    lines: Vec<String>,
+    /// Who wrote this version
+    editor: String,
+    time: DateTime,
+}
+
+impl Version {
+    /// A version of an idea
+    fn idea(
+        title: &str,
+        author: &str,
+        kind: &str,
+        description: &str,
+        editor: String,
+        time: DateTime,
+    ) -> Self {
+        Self::new(
+            vec![
+                format!("Title: {title}"),
+                format!("Author: {author}"),
+                format!("Kind: {kind}"),
+            ],
+            description,
+            editor,
+            time,
+        )
+    }
+
+    /// A version of a comment
+    fn comment(author: &str, content: &str, editor: String, time: DateTime) -> Self {
+        Self::new(vec![format!("Author: {author}")], content, editor, time)
+    }
+
+    /// The `header` lines, followed by the `text` below an empty line
+    fn new(mut header: Vec<String>, text: &str, editor: String, time: DateTime) -> Self {
+        header.push(String::new());
+        header.extend(text.lines().map(str::to_string));
+        Self {
+            lines: header,
+            editor,
+            time,
+        }
+    }
+}
+
+/// How a line differs between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Diff {
+    Kept,
+    Added,
+    Removed,
+}
+
+impl Popup for HistoryPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        frame.render_widget(Clear, area);
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(44), Constraint::Fill(1)])
+            .split(area);
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(match self.statuses.len() {
+                    0 => 0,
+                    n => u16::try_from(n).unwrap_or(u16::MAX).saturating_add(2),
+                }),
+            ])
+            .split(layout[0]);
+
+        let list = List::new(self.versions.iter().enumerate().map(|(i, x)| {
+            format!(
+                "{} {} {}",
+                x.time.format("%d/%m/%Y %H:%M"),
+                if i == 0 { "by" } else { "edit" },
+                x.editor
+            )
+        }))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(style.highlighted)
+                .title("Versions"),
+        )
+        .highlight_style(rataStyle::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, left[0], &mut state);
+
+        let statuses = List::new(self.statuses.iter().map(|x| {
+            format!(
+                "{} {} by {}",
+                x.time.format("%d/%m/%Y %H:%M"),
+                x.status.name(),
+                x.changed_by
+            )
+        }))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(style.not_highlighted)
+                .title("Status changes"),
+        );
+        frame.render_widget(statuses, left[1]);
+
+        let current = &self.versions[self.selected].lines;
+        let lines = match self.selected.checked_sub(1) {
+            Some(previous) => diff(&self.versions[previous].lines, current),
+            None => current.iter().map(|x| (Diff::Kept, x.as_str())).collect(),
+        };
+        let para = Paragraph::new(
+            lines
+                .into_iter()
+                // Ratatui wraps lines of only whitespace into two
+                .map(|(diff, line)| (diff, line.trim_end()))
+                .map(|(diff, line)| match diff {
+                    Diff::Kept if line.is_empty() => Line::raw(""),
+                    Diff::Kept => Line::raw(format!("  {line}")),
+                    Diff::Added => {
+                        Line::styled(format!("+ {line}"), rataStyle::new().fg(Color::Green))
+                    }
+                    Diff::Removed => {
+                        Line::styled(format!("- {line}"), rataStyle::new().fg(Color::Red))
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(style.not_highlighted)
+                .title("Changes from the version before"),
+        );
+        frame.render_widget(para, layout[1]);
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Action::Close,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.versions.len() - 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            _ => (),
+        }
+        Action::Nothing
+    }
+}
+
+impl HistoryPopup {
+    /// Shows the versions of `idea`, which was submitted by `author`, and
+    /// the changes of its status. Every revision is a version that was
+    /// replaced by the edit it records, so each version was written by the
+    /// editor of the revision before it
+    pub fn new(
+        idea: &idea::Model,
+        author: &str,
+        revisions: &[idea_revision::Model],
+        statuses: Vec<status_history::Model>,
+    ) -> Self {
+        let mut editor = author.to_string();
+        let mut time = idea.time;
+        let mut versions = Vec::with_capacity(revisions.len() + 1);
+        for revision in revisions {
+            versions.push(Version::idea(
+                &revision.title,
+                &revision.author,
+                &revision.kind,
+                &revision.description,
+                mem::replace(&mut editor, revision.editor.clone()),
+                time,
+            ));
+            time = revision.time;
+        }
+        versions.push(Version::idea(
+            &idea.title,
+            author,
+            &idea.kind,
+            &idea.description,
+            editor,
+            time,
+        ));
+        Self {
+            selected: versions.len() - 1,
+            versions,
+            statuses,
+        }
+    }
+
+    /// Shows the versions of `comment`, which was written by `author`, like
+    /// [`Self::new`] does for ideas
+    pub fn comment(
+        comment: &comment::Model,
+        author: &str,
+        revisions: &[comment_revision::Model],
+    ) -> Self {
+        let mut editor = author.to_string();
+        let mut time = comment.time;
+        let mut versions = Vec::with_capacity(revisions.len() + 1);
+        for revision in revisions {
+            versions.push(Version::comment(
+                &revision.author,
+                &revision.content,
+                mem::replace(&mut editor, revision.editor.clone()),
+                time,
+            ));
+            time = revision.time;
+        }
+        versions.push(Version::comment(author, &comment.content, editor, time));
+        Self {
+            selected: versions.len() - 1,
+            versions,
+            statuses: Vec::new(),
+        }
+    }
+}
+
+/// The lines of `old` and `new` in order, marked by whether they were kept,
+/// added or removed. Uses the longest common subsequence of the lines
+fn diff<'a>(old: &'a [String], new: &'a [String]) -> Vec<(Diff, &'a str)> {
+    // common[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push((Diff::Kept, old[i].as_str()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push((Diff::Removed, old[i].as_str()));
+            i += 1;
+        } else {
+            lines.push((Diff::Added, new[j].as_str()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|x| (Diff::Removed, x.as_str())));
+    lines.extend(new[j..].iter().map(|x| (Diff::Added, x.as_str())));
+    lines
+}
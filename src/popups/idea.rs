@@ -6,22 +6,33 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use sea_orm::{sqlx::types::chrono, ActiveValue, ConnectOptions, Database, EntityTrait};
+use sea_orm::{
+    sqlx::types::chrono, ActiveValue, DatabaseConnection, EntityTrait, TransactionTrait,
+};
 
 use crate::{
-    entities::{idea, prelude::Idea, sea_orm_active_enums::Issuekind},
+    app::DbActionParts,
+    entities::{
+        idea, idea_identity,
+        prelude::{Idea, IdeaIdentity},
+        sea_orm_active_enums::{Priority, Status},
+    },
     popups::Popup,
     style::Style,
-    view_data::ViewData,
+    view_data::{idea::save_tags, people::find_or_create, ViewData},
 };
 
-use super::Action;
+use super::{kind::KindSelector, person::PersonInput, tags::TagInput, Action};
 
 #[derive(Default, Clone, Debug)]
 pub(crate) struct IdeaPopup {
-    pub(crate) author: String,
+    author: PersonInput,
     pub(crate) title: String,
     pub(crate) description: String,
+    /// Whether the author is hidden from everyone but the moderators
+    anonymous: bool,
+    kind: KindSelector,
+    tags: TagInput,
     selected: Selected,
 }
 
@@ -31,6 +42,9 @@ impl Popup for IdeaPopup {
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Fill(1),
@@ -47,11 +61,28 @@ impl Popup for IdeaPopup {
             } else {
                 style.not_highlighted
             })
-            .title("Author");
-        let para = Paragraph::new(self.author.clone())
-            .block(block)
-            .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[0]);
+            .title(if self.anonymous {
+                "Author (only shown to moderators, can be left empty, \u{2192} to complete)"
+            } else {
+                "Author (\u{2192} to complete)"
+            });
+        self.author.render(block, layout[0], frame);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Anonymous) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Anonymous (space to toggle)");
+        let para = Paragraph::new(if self.anonymous {
+            "[x] Submit without my name"
+        } else {
+            "[ ] Submit without my name"
+        })
+        .block(block);
+        frame.render_widget(para, layout[1]);
 
         let block = base_block
             .clone()
@@ -64,7 +95,27 @@ impl Popup for IdeaPopup {
         let para = Paragraph::new(self.title.clone())
             .block(block)
             .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[1]);
+        frame.render_widget(para, layout[2]);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Kind) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Kind");
+        self.kind.render(block, layout[3], frame);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Tags) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Tags (\u{2192} to complete)");
+        self.tags.render(block, layout[4], frame);
 
         let block = base_block
             .clone()
@@ -77,7 +128,7 @@ impl Popup for IdeaPopup {
         let para = Paragraph::new(self.description.clone())
             .block(block)
             .wrap(Wrap { trim: false });
-        frame.render_widget(para, layout[2]);
+        frame.render_widget(para, layout[5]);
     }
 
     fn handle_input<'a>(&mut self, key: &crossterm::event::KeyEvent) -> Action<'a> {
@@ -87,69 +138,143 @@ impl Popup for IdeaPopup {
                 modifiers: KeyModifiers::CONTROL,
                 ..
             } => {
-                *self.get_str_handle() = self
-                    .get_str_handle()
-                    .rsplit_once(' ')
-                    .map_or(String::new(), |x| x.0.to_string());
+                if let Some(handle) = self.get_str_handle() {
+                    *handle = handle
+                        .rsplit_once(' ')
+                        .map_or(String::new(), |x| x.0.to_string());
+                }
             }
 
             x => match x.code {
                 KeyCode::Esc => return Action::Close,
                 KeyCode::Tab => self.selected = self.selected.next(),
                 KeyCode::BackTab => self.selected = self.selected.prev(),
-                KeyCode::Backspace => drop(self.get_str_handle().pop()),
-                KeyCode::Char(c) => self.get_str_handle().push(c),
+                KeyCode::Left if matches!(self.selected, Selected::Kind) => self.kind.prev(),
+                KeyCode::Right if matches!(self.selected, Selected::Kind) => self.kind.next(),
+                KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right
+                    if matches!(self.selected, Selected::Anonymous) =>
+                {
+                    self.anonymous = !self.anonymous;
+                }
+                KeyCode::Right if matches!(self.selected, Selected::Author) => {
+                    self.author.complete();
+                }
+                KeyCode::Right | KeyCode::Enter if matches!(self.selected, Selected::Tags) => {
+                    self.tags.complete();
+                }
+                KeyCode::Backspace => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.push(c);
+                    }
+                }
                 KeyCode::Enter if matches!(self.selected, Selected::Title) => {
-                    let kind = Issuekind::Issue;
+                    let Some(kind) = self.kind.current().map(str::to_string) else {
+                        return Action::Nothing;
+                    };
+                    let name = self.author.name();
+                    if name.is_empty() && !self.anonymous {
+                        return Action::Nothing;
+                    }
                     let cloned = self.clone();
                     return Action::Db(Box::new(
-                        move |view_data: &mut ViewData, conn_opts: ConnectOptions| {
+                        move |view_data: &mut ViewData, db: DatabaseConnection| {
+                            // The real name of anonymous authors never ends
+                            // up in the idea itself
                             let to_insert = idea::Model {
                                 id: -1,
                                 title: cloned.title.clone(),
                                 description: cloned.description.clone(),
-                                author: cloned.author.clone(),
-                                solved: false,
+                                author_id: (!cloned.anonymous)
+                                    .then(|| view_data.people.id_or_insert(&name)),
+                                status: Status::New,
+                                priority: Priority::Normal,
                                 kind,
                                 time: chrono::Local::now().naive_local(),
+                                edited_at: None,
+                                deleted_at: None,
+                                anonymous: cloned.anonymous,
+                                due: None,
                             };
-                            let id = view_data.idea.new_idea(to_insert.clone());
+                            let anonymous = cloned.anonymous;
+                            let tags = cloned.tags.tags();
+                            let id = view_data.idea.new_idea(to_insert.clone(), tags.clone());
+                            view_data.add_tags(&tags);
 
                             let to_insert_active_model = idea::ActiveModel {
                                 title: ActiveValue::Set(to_insert.title.clone()),
                                 description: ActiveValue::Set(to_insert.description.clone()),
-                                author: ActiveValue::Set(to_insert.author.clone()),
-                                solved: ActiveValue::Set(to_insert.solved),
-                                kind: ActiveValue::Set(to_insert.kind),
+                                status: ActiveValue::Set(to_insert.status),
+                                priority: ActiveValue::Set(to_insert.priority),
+                                kind: ActiveValue::Set(to_insert.kind.clone()),
                                 time: ActiveValue::Set(to_insert.time),
+                                anonymous: ActiveValue::Set(to_insert.anonymous),
                                 ..Default::default()
                             };
                             Some((
                                 id,
-                                (
-                                    async move {
-                                        let db = Database::connect(conn_opts).await?;
-
-                                        let a =
-                                            Idea::insert(to_insert_active_model).exec(&db).await?;
-                                        Ok(Some(a.last_insert_id))
-                                    }
-                                    .boxed(),
-                                    Box::new(
+                                DbActionParts {
+                                    description: format!("Adding the idea {:?}", to_insert.title),
+                                    action: Box::new(move || {
+                                        let db = db.clone();
+                                        let tags = tags.clone();
+                                        let mut to_insert_active_model =
+                                            to_insert_active_model.clone();
+                                        let name = name.clone();
+                                        async move {
+                                            let txn = db.begin().await?;
+                                            // Anonymous authors aren't made
+                                            // people, only their name is kept
+                                            if !anonymous {
+                                                to_insert_active_model.author_id = ActiveValue::Set(
+                                                    Some(find_or_create(&txn, &name).await?),
+                                                );
+                                            }
+                                            let a = Idea::insert(to_insert_active_model)
+                                                .exec(&txn)
+                                                .await?;
+                                            save_tags(&txn, a.last_insert_id, &tags).await?;
+                                            if anonymous && !name.is_empty() {
+                                                IdeaIdentity::insert(idea_identity::ActiveModel {
+                                                    idea: ActiveValue::Set(a.last_insert_id),
+                                                    author: ActiveValue::Set(name),
+                                                })
+                                                // Only moderators may read
+                                                // the row back
+                                                .exec_without_returning(&txn)
+                                                .await?;
+                                            }
+                                            txn.commit().await?;
+                                            Ok(Some(a.last_insert_id))
+                                        }
+                                        .boxed()
+                                    }),
+                                    callback: Box::new(
                                         move |view_data: &mut ViewData, new_id: Option<i32>| {
                                             let _ = view_data.idea.completed(id, |x| {
-                                                x.0.get_entry_mut().id = new_id.expect(
+                                                x.idea.get_entry_mut().id = new_id.expect(
                                                     "This method cannot be called with None",
                                                 );
                                             });
                                         },
                                     ),
-                                ),
+                                    rollback: Box::new(move |view_data: &mut ViewData| {
+                                        view_data.idea.rollback(id, None);
+                                    }),
+                                },
                             ))
                         },
                     ));
                 }
-                KeyCode::Enter => self.get_str_handle().push('\n'),
+                KeyCode::Enter => {
+                    if let Some(handle) = self.get_str_handle() {
+                        handle.push('\n');
+                    }
+                }
                 _ => (),
             },
         }
@@ -158,11 +283,25 @@ impl Popup for IdeaPopup {
 }
 
 impl IdeaPopup {
-    fn get_str_handle(&mut self) -> &mut String {
+    /// An empty form, offering `kinds` to choose from and completing the
+    /// `tags` and `people` that exist
+    pub fn new(kinds: Vec<String>, tags: Vec<String>, people: Vec<String>) -> Self {
+        Self {
+            author: PersonInput::new(people, ""),
+            kind: KindSelector::new(kinds, None),
+            tags: TagInput::new(tags, &[]),
+            ..Self::default()
+        }
+    }
+
+    /// The text of the selected field. `None` if it isn't a text field
+    fn get_str_handle(&mut self) -> Option<&mut String> {
         match self.selected {
-            Selected::Author => &mut self.author,
-            Selected::Title => &mut self.title,
-            Selected::Description => &mut self.description,
+            Selected::Author => Some(&mut self.author.text),
+            Selected::Title => Some(&mut self.title),
+            Selected::Anonymous | Selected::Kind => None,
+            Selected::Tags => Some(&mut self.tags.text),
+            Selected::Description => Some(&mut self.description),
         }
     }
 }
@@ -171,15 +310,21 @@ impl IdeaPopup {
 enum Selected {
     #[default]
     Author,
+    Anonymous,
     Title,
+    Kind,
+    Tags,
     Description,
 }
 
 impl Selected {
     pub const fn next(&self) -> Self {
         match self {
-            Self::Author => Self::Title,
-            Self::Title => Self::Description,
+            Self::Author => Self::Anonymous,
+            Self::Anonymous => Self::Title,
+            Self::Title => Self::Kind,
+            Self::Kind => Self::Tags,
+            Self::Tags => Self::Description,
             Self::Description => Self::Author,
         }
     }
@@ -187,8 +332,11 @@ impl Selected {
     pub const fn prev(&self) -> Self {
         match self {
             Self::Author => Self::Description,
-            Self::Title => Self::Author,
-            Self::Description => Self::Title,
+            Self::Anonymous => Self::Author,
+            Self::Title => Self::Anonymous,
+            Self::Kind => Self::Title,
+            Self::Tags => Self::Kind,
+            Self::Description => Self::Tags,
         }
     }
 }
@@ -0,0 +1,113 @@
+//! The field used for picking ideas by searching for their title
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListState},
+    Frame,
+};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct IdeaPicker {
+    /// What the titles are searched for
+    pub(crate) query: String,
+    /// The id and title of every idea that can be picked
+    ideas: Vec<(i32, String)>,
+    /// The ids of the ideas that are picked
+    picked: Vec<i32>,
+    /// The index of the highlighted idea among the matching ones
+    cursor: usize,
+}
+
+impl IdeaPicker {
+    /// Offers the `ideas`, with the ones in `picked` picked already
+    pub fn new(ideas: Vec<(i32, String)>, picked: Vec<i32>) -> Self {
+        Self {
+            ideas,
+            picked,
+            ..Self::default()
+        }
+    }
+
+    /// The ideas whose title contains every word of the query
+    pub fn matches(&self) -> Vec<&(i32, String)> {
+        let query = self.query.to_lowercase();
+        self.ideas
+            .iter()
+            .filter(|(_, title)| {
+                let title = title.to_lowercase();
+                query.split_whitespace().all(|word| title.contains(word))
+            })
+            .collect()
+    }
+
+    /// The id of the highlighted idea
+    pub fn highlighted(&self) -> Option<i32> {
+        self.matches().get(self.cursor).map(|x| x.0)
+    }
+
+    pub fn picked(&self) -> &[i32] {
+        &self.picked
+    }
+
+    pub fn down(&mut self) {
+        let count = self.matches().len();
+        if count > 0 {
+            self.cursor = (self.cursor + 1) % count;
+        }
+    }
+
+    pub fn up(&mut self) {
+        let count = self.matches().len();
+        if count > 0 {
+            self.cursor = (self.cursor + count - 1) % count;
+        }
+    }
+
+    /// Picks the highlighted idea, or unpicks it if it was picked
+    pub fn toggle(&mut self) {
+        let Some(id) = self.highlighted() else {
+            return;
+        };
+        if let Some(pos) = self.picked.iter().position(|&x| x == id) {
+            self.picked.remove(pos);
+        } else {
+            self.picked.push(id);
+        }
+    }
+
+    /// Changes the query. The first match is highlighted again
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.cursor = 0;
+    }
+
+    pub fn render(&self, block: Block, area: Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Fill(1)])
+            .split(block.inner(area));
+        frame.render_widget(block, area);
+
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled("/", Style::new().dark_gray()),
+                Span::raw(self.query.clone()),
+            ]),
+            layout[0],
+        );
+        let matches = self.matches();
+        let list = List::new(matches.iter().map(|(id, title)| {
+            let mark = if self.picked.contains(id) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+            Line::raw(format!("{mark}{title}"))
+        }))
+        .highlight_style(Style::new().reversed());
+        let mut state =
+            ListState::default().with_selected((!matches.is_empty()).then_some(self.cursor));
+        frame.render_stateful_widget(list, layout[1], &mut state);
+    }
+}
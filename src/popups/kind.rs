@@ -0,0 +1,53 @@
+//! The field used for choosing the kind of an idea
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct KindSelector {
+    kinds: Vec<String>,
+    selected: usize,
+}
+
+impl KindSelector {
+    /// Offers `kinds`, starting at `current`. If `current` isn't one of them,
+    /// e.g. because it was removed from the db, it's kept as an option
+    pub fn new(mut kinds: Vec<String>, current: Option<&str>) -> Self {
+        let selected = match current {
+            Some(current) => kinds.iter().position(|x| x == current).unwrap_or_else(|| {
+                kinds.push(current.to_string());
+                kinds.len() - 1
+            }),
+            None => 0,
+        };
+        Self { kinds, selected }
+    }
+
+    /// The chosen kind. `None` if there are none to choose from
+    pub fn current(&self) -> Option<&str> {
+        self.kinds.get(self.selected).map(String::as_str)
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.kinds.len().max(1);
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or_else(|| self.kinds.len().saturating_sub(1));
+    }
+
+    pub fn render(&self, block: Block, area: Rect, frame: &mut Frame) {
+        let line = Line::from(vec![
+            Span::raw("\u{2190} "),
+            Span::raw(self.current().unwrap_or("no kinds in the db")),
+            Span::raw(" \u{2192}"),
+        ]);
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+}
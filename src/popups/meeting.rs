@@ -0,0 +1,186 @@
+//! The popup that appears when you want to plan a meeting, or change when it
+//! is held and who attends
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    popups::Popup,
+    style::Style,
+    view_data::{
+        meeting::{Meeting, Meetings},
+        people::People,
+    },
+};
+
+use super::{date::DateInput, tags::TagInput, Action};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct MeetingPopup {
+    /// The meeting that is changed. `None` for a new one
+    id: Option<i32>,
+    pub(crate) title: String,
+    time: DateInput,
+    attendees: TagInput,
+    selected: Selected,
+}
+
+impl Popup for MeetingPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        for area in layout.iter() {
+            frame.render_widget(Clear, *area);
+        }
+        let base_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+        let border_style = |selected: Selected| {
+            if self.selected == selected {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            }
+        };
+
+        let block = base_block
+            .clone()
+            .border_style(border_style(Selected::Title))
+            .title("Title");
+        frame.render_widget(Paragraph::new(self.title.clone()).block(block), layout[0]);
+
+        let block = base_block
+            .clone()
+            .border_style(border_style(Selected::Time))
+            .title("When (\u{2191}/\u{2193} to change the day)");
+        self.time.render(block, layout[1], frame);
+
+        let block = base_block
+            .clone()
+            .border_style(border_style(Selected::Attendees))
+            .title("Attendees (separated by commas, \u{2192} to complete)");
+        self.attendees.render(block, layout[2], frame);
+
+        frame.render_widget(
+            Paragraph::new("Tab to switch fields, Enter to save"),
+            layout[3],
+        );
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                *self.get_str_handle() = self
+                    .get_str_handle()
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Tab => self.selected = self.selected.next(),
+                KeyCode::BackTab => self.selected = self.selected.prev(),
+                KeyCode::Up if self.selected == Selected::Time => self.time.step(true),
+                KeyCode::Down if self.selected == Selected::Time => self.time.step(false),
+                KeyCode::Right if self.selected == Selected::Attendees => {
+                    self.attendees.complete();
+                }
+                KeyCode::Backspace => drop(self.get_str_handle().pop()),
+                KeyCode::Char(c) => self.get_str_handle().push(c),
+                KeyCode::Enter => {
+                    // Accept what is being suggested, like in the other forms
+                    if self.selected == Selected::Attendees && self.attendees.complete() {
+                        return Action::Nothing;
+                    }
+                    let title = self.title.trim();
+                    if title.is_empty() {
+                        self.selected = Selected::Title;
+                        return Action::Nothing;
+                    }
+                    let Ok(Some(time)) = self.time.value() else {
+                        self.selected = Selected::Time;
+                        return Action::Nothing;
+                    };
+                    return Action::Db(Meetings::save(
+                        self.id,
+                        title.to_string(),
+                        time,
+                        self.attendees.tags(),
+                    ));
+                }
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl MeetingPopup {
+    /// A form for planning a new meeting, completing the `known` attendees
+    pub fn new(known: Vec<String>) -> Self {
+        Self {
+            attendees: TagInput::names(known, &[]),
+            time: DateInput::new(None, true),
+            ..Self::default()
+        }
+    }
+
+    /// A form for changing `meeting`, whose attendees are among `people`
+    pub fn edit(meeting: &Meeting, people: &People, known: Vec<String>) -> Self {
+        Self {
+            id: Some(meeting.meeting.id),
+            title: meeting.meeting.title.clone(),
+            time: DateInput::new(Some(meeting.meeting.time), true),
+            attendees: TagInput::names(known, &meeting.attendee_names(people)),
+            selected: Selected::Title,
+        }
+    }
+
+    fn get_str_handle(&mut self) -> &mut String {
+        match self.selected {
+            Selected::Title => &mut self.title,
+            Selected::Time => &mut self.time.text,
+            Selected::Attendees => &mut self.attendees.text,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+enum Selected {
+    #[default]
+    Title,
+    Time,
+    Attendees,
+}
+
+impl Selected {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Title => Self::Time,
+            Self::Time => Self::Attendees,
+            Self::Attendees => Self::Title,
+        }
+    }
+
+    pub const fn prev(self) -> Self {
+        match self {
+            Self::Title => Self::Attendees,
+            Self::Time => Self::Title,
+            Self::Attendees => Self::Time,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! The popup that appears when you want to merge two people that are really
+//! the same person
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    entities::person,
+    popups::Popup,
+    style::Style,
+    view_data::{people::People, ViewData},
+};
+
+use super::{person::PersonInput, Action};
+
+#[derive(Clone, Debug)]
+pub(crate) struct MergePopup {
+    /// The person that goes away
+    from: person::Model,
+    /// Who they are merged into
+    into: PersonInput,
+}
+
+impl Popup for MergePopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(1)])
+            .split(area);
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(Clear, layout[1]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title(format!(
+                "Merge {} into (\u{2192} to complete)",
+                self.from.name
+            ));
+        self.into.render(block, layout[0], frame);
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Everything by {} will be by them instead, Enter to merge",
+                self.from.name
+            )),
+            layout[1],
+        );
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.into.text = self
+                    .into
+                    .text
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Right => {
+                    self.into.complete();
+                }
+                KeyCode::Backspace => {
+                    self.into.text.pop();
+                }
+                KeyCode::Char(c) => self.into.text.push(c),
+                KeyCode::Enter => {
+                    // Only people that exist can be merged into, so what
+                    // is typed is completed
+                    let name = self
+                        .into
+                        .completion()
+                        .map_or_else(|| self.into.name(), str::to_string);
+                    let from = self.from.id;
+                    return Action::Db(Box::new(
+                        move |view_data: &mut ViewData, db: DatabaseConnection| {
+                            let into = view_data.people.find(&name)?.id;
+                            People::merge(from, into)(view_data, db)
+                        },
+                    ));
+                }
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl MergePopup {
+    /// Asks who `from` should be merged into, completing the `people` that
+    /// exist
+    pub fn new(from: person::Model, people: Vec<String>) -> Self {
+        Self {
+            into: PersonInput::new(people.into_iter().filter(|x| *x != from.name).collect(), ""),
+            from,
+        }
+    }
+}
@@ -5,9 +5,23 @@ use ratatui::{layout::Rect, Frame};
 
 use crate::{app::DbActionReturn, style::Style};
 
-pub mod idea;
+pub mod assign;
 pub mod comment;
+pub mod confirm;
+pub mod date;
+pub mod decision;
 pub mod edit;
+pub mod history;
+pub mod idea;
+pub mod idea_picker;
+pub mod kind;
+pub mod meeting;
+pub mod merge;
+pub mod overdue;
+pub mod person;
+pub mod pick_ideas;
+pub mod roster;
+pub mod tags;
 
 /// A trait describing a popup. This is used for storing the popup more easily in `App`
 pub trait Popup: fmt::Debug {
@@ -0,0 +1,70 @@
+//! The popup shown on startup when ideas became overdue since the last
+//! session
+use crossterm::event::KeyEvent;
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use sea_orm::prelude::DateTime;
+
+use crate::{entities::idea, popups::Popup, style::Style};
+
+use super::Action;
+
+#[derive(Clone, Debug)]
+pub(crate) struct OverduePopup {
+    /// The title and due date of every idea that became overdue
+    ideas: Vec<(String, DateTime)>,
+}
+
+impl Popup for OverduePopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        frame.render_widget(Clear, area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title(match self.ideas.len() {
+                1 => "1 idea became overdue since you last looked".to_string(),
+                n => format!("{n} ideas became overdue since you last looked"),
+            });
+        let lines = self
+            .ideas
+            .iter()
+            .map(|(title, due)| {
+                Line::from(vec![
+                    Span::raw(format!("{} ", due.format("%d/%m/%Y"))).red(),
+                    Span::raw(title.clone()),
+                ])
+            })
+            .chain([
+                Line::raw(""),
+                Line::raw("Any key to close. f shows everything that is due soon").dark_gray(),
+            ])
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn handle_input<'a>(&mut self, _: &KeyEvent) -> Action<'a> {
+        Action::Close
+    }
+}
+
+impl OverduePopup {
+    /// Lists the `ideas`, which are overdue. `None` if there are none
+    pub fn new(ideas: &[&idea::Model]) -> Option<Self> {
+        (!ideas.is_empty()).then(|| Self {
+            ideas: ideas
+                .iter()
+                .filter_map(|x| Some((x.title.clone(), x.due?)))
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,102 @@
+//! The field used for who wrote something, completing the people that exist
+//! even when they are misspelled a bit
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use crate::view_data::people::normalize;
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct PersonInput {
+    /// The name as typed
+    pub(crate) text: String,
+    /// The names of everyone that exists, sorted
+    known: Vec<String>,
+}
+
+impl PersonInput {
+    /// Starts out with `name`, completing the `known` people
+    pub fn new(known: Vec<String>, name: &str) -> Self {
+        Self {
+            text: name.to_string(),
+            known,
+        }
+    }
+
+    /// The entered name. People that exist are spelled the way they were
+    /// added
+    pub fn name(&self) -> String {
+        let name = normalize(&self.text);
+        self.known
+            .iter()
+            .find(|x| x.to_lowercase() == name.to_lowercase())
+            .map_or(name, Clone::clone)
+    }
+
+    /// The person that matches what is typed best, unless it's typed out
+    /// already
+    pub fn completion(&self) -> Option<&str> {
+        let name = normalize(&self.text).to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        self.known
+            .iter()
+            .filter_map(|x| Some((fuzzy_score(&name, x)?, x)))
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.len().cmp(&a.1.len())))
+            .map(|x| x.1.as_str())
+            .filter(|x| x.to_lowercase() != name)
+    }
+
+    /// Replaces the name by its completion. Returns whether there was one
+    pub fn complete(&mut self) -> bool {
+        let Some(name) = self.completion().map(str::to_string) else {
+            return false;
+        };
+        self.text = name;
+        true
+    }
+
+    pub fn render(&self, block: Block, area: Rect, frame: &mut Frame) {
+        // Completions that start with what is typed are finished in place,
+        // the others are shown after it
+        let rest = self.completion().map_or(String::new(), |name| {
+            if name.to_lowercase().starts_with(&self.text.to_lowercase()) {
+                name.chars().skip(self.text.chars().count()).collect()
+            } else {
+                format!("  \u{2192} {name}")
+            }
+        });
+        let line = Line::from(vec![
+            Span::raw(self.text.clone()),
+            Span::styled(rest, Style::new().dark_gray()),
+        ]);
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+}
+
+/// How well `name` matches `query`, which is lowercase. `None` if the
+/// letters of `query` don't appear in `name` in order. Letters starting a
+/// word and letters following each other count more, skipped ones count
+/// against it
+fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut pos = 0;
+    for c in query.chars().filter(|x| !x.is_whitespace()) {
+        let found = pos + name.get(pos..)?.iter().position(|&x| x == c)?;
+        if found == 0 || name[found - 1] == ' ' {
+            score += 3;
+        }
+        if found > 0 && found == pos {
+            score += 2;
+        }
+        score -= i32::try_from(found - pos).unwrap_or(i32::MAX);
+        pos = found + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,114 @@
+//! The popup that appears when you want to pick a number of ideas, like the
+//! ones that came out of talking to someone on the roster or the ones on the
+//! agenda of a meeting
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    popups::Popup,
+    style::Style,
+    view_data::{meeting::Meetings, roster::Roster},
+};
+
+use super::{idea_picker::IdeaPicker, Action};
+
+#[derive(Clone, Debug)]
+pub(crate) struct PickIdeasPopup {
+    title: String,
+    picker: IdeaPicker,
+    /// What the picked ideas are saved as
+    purpose: Picking,
+}
+
+/// What the ideas are picked for
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Picking {
+    /// The ideas that came out of talking to the person with the id
+    Conversation(i32),
+    /// The agenda of the meeting with the id, in the order they are picked
+    Agenda(i32),
+}
+
+impl Popup for PickIdeasPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        frame.render_widget(Clear, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(1)])
+            .split(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(style.highlighted)
+            .title(self.title.clone());
+        self.picker.render(block, layout[0], frame);
+        frame.render_widget(
+            Paragraph::new("Type to search, Tab to pick or unpick, Enter to save"),
+            layout[1],
+        );
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                let query = self
+                    .picker
+                    .query
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+                self.picker.set_query(query);
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Down => self.picker.down(),
+                KeyCode::Up => self.picker.up(),
+                KeyCode::Tab => self.picker.toggle(),
+                KeyCode::Backspace => {
+                    let mut query = self.picker.query.clone();
+                    query.pop();
+                    self.picker.set_query(query);
+                }
+                KeyCode::Char(c) => {
+                    let query = format!("{}{c}", self.picker.query);
+                    self.picker.set_query(query);
+                }
+                KeyCode::Enter => {
+                    let picked = self.picker.picked().to_vec();
+                    return Action::Db(match self.purpose {
+                        Picking::Conversation(person) => Roster::link_ideas(person, picked),
+                        Picking::Agenda(meeting) => Meetings::set_agenda(meeting, picked),
+                    });
+                }
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl PickIdeasPopup {
+    /// Offers the `ideas` for `purpose`, with the ones in `picked` picked
+    /// already. `title` says what they are picked for
+    pub fn new(
+        title: String,
+        purpose: Picking,
+        ideas: Vec<(i32, String)>,
+        picked: Vec<i32>,
+    ) -> Self {
+        Self {
+            title,
+            picker: IdeaPicker::new(ideas, picked),
+            purpose,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! The popup that appears when you want to change the class of someone on
+//! the roster, or write down what came up when talking to them
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{entities::roster, popups::Popup, style::Style, view_data::roster::Roster};
+
+use super::Action;
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct RosterPopup {
+    person: i32,
+    name: String,
+    pub(crate) class: String,
+    pub(crate) notes: String,
+    selected: Selected,
+}
+
+impl Popup for RosterPopup {
+    fn render(&self, style: Style, area: ratatui::prelude::Rect, frame: &mut Frame) {
+        frame.render_widget(Clear, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Fill(1)])
+            .split(area);
+        let base_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Class) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title(format!("Class of {}", self.name));
+        let para = Paragraph::new(self.class.clone()).block(block);
+        frame.render_widget(para, layout[0]);
+
+        let block = base_block
+            .clone()
+            .border_style(if matches!(self.selected, Selected::Notes) {
+                style.highlighted
+            } else {
+                style.not_highlighted
+            })
+            .title("Notes");
+        let para = Paragraph::new(self.notes.clone())
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(para, layout[1]);
+    }
+
+    fn handle_input<'a>(&mut self, key: &KeyEvent) -> Action<'a> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                *self.get_str_handle() = self
+                    .get_str_handle()
+                    .rsplit_once(' ')
+                    .map_or(String::new(), |x| x.0.to_string());
+            }
+
+            x => match x.code {
+                KeyCode::Esc => return Action::Close,
+                KeyCode::Tab | KeyCode::BackTab => self.selected = self.selected.next(),
+                KeyCode::Backspace => drop(self.get_str_handle().pop()),
+                KeyCode::Char(c) => self.get_str_handle().push(c),
+                KeyCode::Enter if matches!(self.selected, Selected::Class) => {
+                    return Action::Db(Roster::edit(
+                        self.person,
+                        self.class.trim().to_string(),
+                        self.notes.trim_end().to_string(),
+                    ));
+                }
+                KeyCode::Enter => self.get_str_handle().push('\n'),
+                _ => (),
+            },
+        }
+        Action::Nothing
+    }
+}
+
+impl RosterPopup {
+    /// A form for changing what is known about `entry`, who is called `name`
+    pub fn new(entry: &roster::Model, name: &str) -> Self {
+        Self {
+            person: entry.person,
+            name: name.to_string(),
+            class: entry.class.clone(),
+            notes: entry.notes.clone(),
+            // Notes are what is usually written after talking to someone
+            selected: Selected::Notes,
+        }
+    }
+
+    fn get_str_handle(&mut self) -> &mut String {
+        match self.selected {
+            Selected::Class => &mut self.class,
+            Selected::Notes => &mut self.notes,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+enum Selected {
+    #[default]
+    Class,
+    Notes,
+}
+
+impl Selected {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Class => Self::Notes,
+            Self::Notes => Self::Class,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! The field used for tagging an idea, completing the tags that already exist.
+//! It's also used for other lists of words, like who is assigned to an idea
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct TagInput {
+    /// The tags separated by spaces or commas, as typed
+    pub(crate) text: String,
+    /// The tags that exist, sorted
+    known: Vec<String>,
+    /// Whether only commas separate the tags, so they can be names with
+    /// spaces in them
+    names: bool,
+}
+
+impl TagInput {
+    /// Starts out with `tags`, completing the `known` ones
+    pub fn new(known: Vec<String>, tags: &[String]) -> Self {
+        Self {
+            text: tags.join(" "),
+            known,
+            names: false,
+        }
+    }
+
+    /// Starts out with the `names`, completing the `known` ones. Unlike
+    /// tags, they are only separated by commas
+    pub fn names(known: Vec<String>, names: &[String]) -> Self {
+        Self {
+            text: names.join(", "),
+            known,
+            names: true,
+        }
+    }
+
+    /// The entered tags, sorted and without duplicates. Tags that exist are
+    /// spelled the way they were created
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = self
+            .words()
+            .map(|word| {
+                self.known
+                    .iter()
+                    .find(|x| x.eq_ignore_ascii_case(word))
+                    .map_or_else(|| word.to_string(), Clone::clone)
+            })
+            .collect::<Vec<_>>();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// The known tag starting with the word that is being typed
+    pub fn completion(&self) -> Option<&str> {
+        let word = self.typing().to_lowercase();
+        if word.is_empty() {
+            return None;
+        }
+        self.known
+            .iter()
+            .find(|x| x.len() > word.len() && x.to_lowercase().starts_with(&word))
+            .map(String::as_str)
+    }
+
+    /// Replaces the word that is being typed by its completion. Returns
+    /// whether there was one
+    pub fn complete(&mut self) -> bool {
+        let Some(tag) = self.completion().map(str::to_string) else {
+            return false;
+        };
+        let start = self.text.rfind(self.separators()).map_or(0, |x| x + 1);
+        self.text.truncate(start);
+        if self.names && start > 0 {
+            self.text.push(' ');
+        }
+        self.text.push_str(&tag);
+        self.text.push_str(if self.names { ", " } else { " " });
+        true
+    }
+
+    pub fn render(&self, block: Block, area: Rect, frame: &mut Frame) {
+        let rest = self.completion().map_or(String::new(), |tag| {
+            tag.chars().skip(self.typing().chars().count()).collect()
+        });
+        let line = Line::from(vec![
+            Span::raw(self.text.clone()),
+            Span::styled(rest, Style::new().dark_gray()),
+        ]);
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+
+    /// The word after the last separator
+    fn typing(&self) -> &str {
+        self.text
+            .rsplit(self.separators())
+            .next()
+            .unwrap_or_default()
+            .trim_start()
+            .trim_start_matches('#')
+    }
+
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split(self.separators())
+            .map(|x| x.trim().trim_start_matches('#'))
+            .filter(|x| !x.is_empty())
+    }
+
+    fn separators(&self) -> &'static [char] {
+        if self.names {
+            &[',']
+        } else {
+            &[' ', ',']
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! What is remembered between sessions, kept in
+//! `$XDG_STATE_HOME/verneanbud/last_sessions`. Every database has a line of
+//! its own
+use core::fmt;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use sea_orm::prelude::DateTime;
+
+/// How times are written to the file
+const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// When the last session on the database at `database_url` ended. `None` if
+/// there was none, or if the file can't be read, as nothing depends on it
+pub fn last_session(database_url: &str) -> Option<DateTime> {
+    let content = fs::read_to_string(path()?).ok()?;
+    let database = key(database_url);
+    content.lines().find_map(|line| {
+        let (time, x) = line.split_once('\t')?;
+        (x == database)
+            .then(|| DateTime::parse_from_str(time, FORMAT).ok())
+            .flatten()
+    })
+}
+
+/// Remembers that a session on the database at `database_url` ended at
+/// `time`
+pub fn save_session(database_url: &str, time: DateTime) -> Result<(), StateError> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+    let database = key(database_url);
+    // The other databases keep their lines. A file that can't be read is
+    // started over
+    let mut content = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| line.split_once('\t').is_some_and(|(_, x)| x != database))
+        .fold(String::new(), |content, line| content + line + "\n");
+    content += &format!("{}\t{database}\n", time.format(FORMAT));
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, content));
+    result.map_err(|err| StateError { path, err })
+}
+
+/// What the database at `url` is known by in the file. The password has no
+/// business being there, and the options don't change the database
+fn key(url: &str) -> String {
+    let url = url.split_once('?').map_or(url, |x| x.0);
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    match rest.rsplit_once('@') {
+        Some((credentials, host)) => {
+            let user = credentials.split_once(':').map_or(credentials, |x| x.0);
+            format!("{scheme}://{user}@{host}")
+        }
+        None => url.to_string(),
+    }
+}
+
+/// Where the state is kept. `None` if there is no home to keep it in
+fn path() -> Option<PathBuf> {
+    env::var_os("XDG_STATE_HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("state")))
+        .map(|dir| dir.join("verneanbud").join("last_sessions"))
+}
+
+/// The state couldn't be written
+#[derive(Debug)]
+pub struct StateError {
+    path: PathBuf,
+    err: io::Error,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not write {}: {}", self.path.display(), self.err)
+    }
+}
+
+impl std::error::Error for StateError {}
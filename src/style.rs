@@ -1,9 +1,15 @@
-use ratatui::style::{Color, Style as rataStyle};
+use ratatui::style::{Color, Modifier, Style as rataStyle};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Style {
     pub highlighted: rataStyle,
     pub not_highlighted: rataStyle,
+    /// Used for entries with a db action in flight
+    pub pending: rataStyle,
+    /// Used for entries whose db action failed
+    pub failed: rataStyle,
+    /// Used for ideas that should have been followed up already
+    pub overdue: rataStyle,
 }
 
 impl Default for Style {
@@ -11,6 +17,12 @@ impl Default for Style {
         Self {
             highlighted: rataStyle::new().fg(Color::Yellow),
             not_highlighted: rataStyle::new().fg(Color::White),
+            pending: rataStyle::new()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+            failed: rataStyle::new().fg(Color::Red),
+            // Dark, so the coloured text on it stays readable
+            overdue: rataStyle::new().bg(Color::Indexed(52)),
         }
     }
 }
@@ -0,0 +1,61 @@
+use ratatui::{
+    style::Style,
+    widgets::{Block, BorderType, Clear, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use ratatui::prelude::*;
+
+use crate::{app::App, error_log::ErrorLog};
+
+/// Renders the error log as a popup
+pub fn render(app: &App, frame: &mut Frame, area: Rect) {
+    let log = &app.error_log;
+    frame.render_widget(Clear, area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(5)])
+        .split(area);
+
+    let list = List::new(log.entries().map(|entry| {
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", entry.time.format("%H:%M:%S")),
+                Style::new().red(),
+            ),
+            Span::raw(format!("{} failed", entry.operation)),
+            if entry.retry.is_some() {
+                Span::styled(" (r to retry)", Style::new().dark_gray())
+            } else {
+                Span::raw("")
+            },
+        ])
+    }))
+    .block(
+        Block::bordered()
+            .title("Errors")
+            .title_bottom("r: retry, d: discard and undo, q: close")
+            .border_type(BorderType::Rounded)
+            .border_style(app.style.highlighted),
+    )
+    .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(selected(log));
+    frame.render_stateful_widget(list, layout[0], &mut list_state);
+
+    let error = log
+        .entries()
+        .nth(log.selected)
+        .map_or("Nothing has gone wrong", |x| x.error.as_str());
+    frame.render_widget(
+        Paragraph::new(error).wrap(Wrap { trim: false }).block(
+            Block::bordered()
+                .title("Details")
+                .border_type(BorderType::Rounded),
+        ),
+        layout[1],
+    );
+}
+
+fn selected(log: &ErrorLog) -> Option<usize> {
+    log.entries().next().map(|_| log.selected)
+}
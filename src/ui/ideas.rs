@@ -7,10 +7,11 @@ use ratatui::{
 };
 
 use ratatui::prelude::*;
+use sea_orm::sqlx::types::chrono;
 
 use crate::{
-    app::App,
-    entities::sea_orm_active_enums::Issuekind,
+    app::{App, View},
+    entities::sea_orm_active_enums::{Priority, Status},
     view_data::{db_type::DbType, search_query::SearchQuery},
 };
 
@@ -25,41 +26,177 @@ pub fn render(app: &App, frame: &mut Frame, mainview: Rect, infoview: Rect) {
     } else {
         render_select(app, frame, mainview);
     }
-    render_infoview(app, frame, infoview);
+    if app.view == View::Mine {
+        let counts = app.view_data.idea.open_per_assignee();
+        let height = u16::try_from(counts.len().max(1) + 2).unwrap_or(u16::MAX);
+        let info_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Fill(1)])
+            .split(infoview);
+        render_open_counts(app, &counts, frame, info_layout[0]);
+        render_infoview(app, frame, info_layout[1]);
+    } else {
+        render_infoview(app, frame, infoview);
+    }
+}
+
+/// How many open ideas everyone has assigned
+fn render_open_counts(app: &App, counts: &[(String, usize)], frame: &mut Frame, view: Rect) {
+    let max_name_len = counts
+        .iter()
+        .map(|x| x.0.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines = if counts.is_empty() {
+        vec![Line::raw("Nobody has open ideas assigned")]
+    } else {
+        counts
+            .iter()
+            .map(|(name, count)| {
+                let line = Line::raw(format!("{name:max_name_len$} {count}"));
+                if *name == app.user {
+                    line.bold()
+                } else {
+                    line
+                }
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::bordered()
+                .title("Open ideas per assignee")
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        ),
+        view,
+    );
 }
 
 fn render_infoview(app: &App, frame: &mut Frame, view: Rect) {
     if let Some(selected_idea) = app.view_data.idea.current() {
-        let raw_text = selected_idea.0.get_entry().description.clone();
-        let widget = Paragraph::new(Text::from(
-            raw_text
-                .lines()
-                .map(Span::raw)
-                .chain([Span::styled(
-                    "\u{2500}".repeat(50),
-                    Style::new().fg(Color::Green),
-                )])
-                .chain(selected_idea.1.iter().map(DbType::get_entry).flat_map(|x| {
-                    iter::once(Span::styled(
-                        format!(
-                            "{}, ({})",
-                            x.author.clone(),
-                            x.time.format("%d/%m/%Y [%H:%m]")
+        let raw_text = selected_idea.idea.get_entry().description.clone();
+        let thread = app.view_data.idea.thread(&selected_idea.comments);
+        let cursor = app.view_data.idea.comment_cursor();
+        let tags =
+            (!selected_idea.tags.is_empty()).then(|| Line::from(tag_chips(&selected_idea.tags)));
+        // Only ever filled in for moderators
+        let identity = app
+            .revealed
+            .get(&selected_idea.idea.get_entry().id)
+            .map(|author| {
+                Line::styled(
+                    author.as_ref().map_or_else(
+                        || "The author didn't give their name".to_string(),
+                        |author| format!("Submitted anonymously by {author}"),
+                    ),
+                    Style::new().fg(Color::Magenta).italic(),
+                )
+            });
+        let talked_to = app
+            .view_data
+            .roster
+            .people_with_idea(selected_idea.idea.get_entry().id)
+            .map(|x| app.view_data.people.name(x))
+            .collect::<Vec<_>>();
+        let conversations = (!talked_to.is_empty()).then(|| {
+            Line::styled(
+                format!("From talking to {}", talked_to.join(", ")),
+                Style::new().fg(Color::Cyan),
+            )
+        });
+        let due = selected_idea.idea.get_entry().due.map(|due| {
+            Line::styled(
+                format!("Follow up by {}", due.format("%d/%m/%Y")),
+                if selected_idea
+                    .idea
+                    .get_entry()
+                    .is_overdue(chrono::Local::now().date_naive())
+                {
+                    Style::new().fg(Color::Red).bold()
+                } else {
+                    Style::new().fg(Color::Yellow)
+                },
+            )
+        });
+        let assignees = (!selected_idea.assignees.is_empty()).then(|| {
+            Line::styled(
+                format!("Assigned to {}", selected_idea.assignees.join(", ")),
+                Style::new().fg(Color::Blue),
+            )
+        });
+        let widget =
+            Paragraph::new(Text::from(
+                identity
+                    .into_iter()
+                    .chain(conversations)
+                    .chain(due)
+                    .chain(assignees)
+                    .chain(tags)
+                    .chain(
+                        raw_text
+                            .lines()
+                            .map(Span::raw)
+                            .chain([Span::styled(
+                                "\u{2500}".repeat(50),
+                                Style::new().fg(Color::Green),
+                            )])
+                            .chain(thread.into_iter().enumerate().flat_map(|(i, entry)| {
+                                let x = entry.comment.get_entry();
+                                let style = entry_style(app, entry.comment);
+                                let indent = "  ".repeat(entry.depth);
+                                let header_style = if cursor == Some(i) {
+                                    style.bold().reversed()
+                                } else {
+                                    style.bold().underlined()
+                                };
+                                let hidden = match entry.hidden {
+                                    0 => String::new(),
+                                    1 => " \u{25b8} 1 reply".to_string(),
+                                    n => format!(" \u{25b8} {n} replies"),
+                                };
+                                iter::once(Span::styled(
+                                    format!(
+                                        "{indent}{}{}, ({}){}{hidden}",
+                                        failed_marker(entry.comment),
+                                        app.view_data.people.name(x.author_id),
+                                        x.time.format("%d/%m/%Y [%H:%m]"),
+                                        if x.edited_at.is_some() {
+                                            " (edited)"
+                                        } else {
+                                            ""
+                                        }
+                                    ),
+                                    header_style,
+                                ))
+                                .chain(
+                                    x.content
+                                        .lines()
+                                        .map(move |x| format!("{indent} {x}"))
+                                        .map(move |x| Span::styled(x, style)),
+                                )
+                            }))
+                            .map(Line::from),
+                    )
+                    .collect::<Vec<_>>(),
+            ))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title(format!(
+                        "Description ({}{}{})",
+                        selected_idea.idea.get_entry().status.name(),
+                        selected_idea.idea.get_entry().deleted_at.map_or(
+                            String::new(),
+                            |x| format!(", deleted {}", x.format("%d/%m/%Y %H:%M"))
                         ),
-                        Style::new().bold().underlined(),
+                        selected_idea.idea.get_entry().edited_at.map_or(
+                            String::new(),
+                            |x| format!(", edited {}, h for history", x.format("%d/%m/%Y %H:%M"))
+                        )
                     ))
-                    .chain(x.content.lines().map(|x| format!(" {x}")).map(Span::raw))
-                }))
-                .map(Line::from)
-                .collect::<Vec<_>>(),
-        ))
-        .wrap(Wrap { trim: false })
-        .block(
-            Block::bordered()
-                .title("Description")
-                .border_type(ratatui::widgets::BorderType::Rounded),
-        )
-        .scroll((selected_idea.2, 0));
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            )
+            .scroll((selected_idea.scroll, 0));
         frame.render_widget(widget, view);
     } else {
         let text = Paragraph::new("Select an entry to view").block(
@@ -79,36 +216,95 @@ fn render_select(app: &App, frame: &mut Frame, view: Rect) {
             .selected
             .map(|x| ideas.clone().count() - x - 1),
     );
-    let max_title_len = ideas.clone().map(|x| x.title.len()).max().unwrap_or(0);
+    let max_title_len = ideas
+        .clone()
+        .map(|x| x.idea.get_entry().title.len())
+        .max()
+        .unwrap_or(0);
+
+    let max_kind_len = ideas
+        .clone()
+        .map(|x| x.idea.get_entry().kind.chars().count())
+        .max()
+        .unwrap_or(0);
 
-    let max_author_len = ideas.clone().map(|x| x.author.len()).max().unwrap_or(0);
+    let max_author_len = ideas
+        .clone()
+        .map(|x| app.view_data.author(x.idea.get_entry()).chars().count())
+        .max()
+        .unwrap_or(0);
 
+    let max_votes_len = ideas
+        .clone()
+        .map(|x| x.voters.len().to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let today = chrono::Local::now().date_naive();
     let list = List::new(
         ideas
             .clone()
-            .map(|idea| {
+            .map(|entry| {
+                let idea = entry.idea.get_entry();
                 let title = &idea.title;
-                let kind = kind_str(&idea.kind).to_string();
-                let author = idea.author.clone();
+                let kind = format!("{:max_kind_len$}", idea.kind);
+                let author = app.view_data.author(idea);
                 let time = idea.time.format("%d/%m");
                 // format!("{author}: {title:max_title_len$} | {state:10}| {kind}")
-                Line::from(Vec::from([
-                    Span::styled(format!("{time} "), Style::new().red()),
-                    Span::raw(kind),
-                    if idea.solved {
-                        Span::styled(" \u{f41d} ", Style::new().magenta())
-                    } else {
-                        Span::styled(" \u{f41b} ", Style::new().green())
-                    },
-                    Span::styled(format!("{author:>max_author_len$}: "), Style::new().blue()),
-                    Span::raw(format!("{title:max_title_len$} ")),
-                ]))
+                Line::from(
+                    [
+                        Span::raw(failed_marker(&entry.idea)),
+                        priority_marker(idea.priority),
+                        Span::styled(format!("{time} "), Style::new().red()),
+                        Span::raw(kind),
+                        status_icon(idea.status),
+                        vote_count(app, &entry.voters, max_votes_len),
+                        Span::styled(
+                            format!("{author:>max_author_len$}: "),
+                            if idea.anonymous {
+                                Style::new().dark_gray().italic()
+                            } else {
+                                Style::new().blue()
+                            },
+                        ),
+                        Span::raw(format!("{title:max_title_len$} ")),
+                    ]
+                    .into_iter()
+                    .chain(idea.due.map(|due| {
+                        Span::styled(
+                            format!("due {} ", due.format("%d/%m")),
+                            Style::new().yellow(),
+                        )
+                    }))
+                    .chain(tag_chips(&entry.tags))
+                    .collect::<Vec<_>>(),
+                )
+                .style(if idea.is_overdue(today) {
+                    entry_style(app, &entry.idea).patch(app.style.overdue)
+                } else {
+                    entry_style(app, &entry.idea)
+                })
             })
             .rev(),
     )
     .block(
         Block::bordered()
-            .title("List")
+            .title(format!(
+                "{} (by {}){}",
+                match app.view {
+                    View::Ideas | View::Roster | View::Meetings => "List".to_string(),
+                    View::Mine => format!("Assigned to {}", app.user),
+                    View::Trash => "Trash".to_string(),
+                    View::DueSoon => "Due within a week".to_string(),
+                },
+                // Ideas that are due soon are always listed soonest first
+                if app.view == View::DueSoon {
+                    "due date"
+                } else {
+                    app.view_data.idea.sort().name()
+                },
+                if app.refreshing { " (refreshing)" } else { "" }
+            ))
             .style(Color::White)
             .border_type(ratatui::widgets::BorderType::Rounded),
     )
@@ -118,13 +314,89 @@ fn render_select(app: &App, frame: &mut Frame, view: Rect) {
     frame.render_stateful_widget(list, view, &mut list_state);
 }
 
+/// How an entry is shown depending on whether it's in the db
+fn entry_style<T: Clone>(app: &App, entry: &DbType<T>) -> Style {
+    match entry {
+        DbType::InDb(_) => Style::new(),
+        DbType::DbActionPending(..) => app.style.pending,
+        DbType::Failed(..) => app.style.failed,
+    }
+}
+
+/// Put in front of entries whose db action failed
+const fn failed_marker<T: Clone>(entry: &DbType<T>) -> &'static str {
+    if matches!(entry, DbType::Failed(..)) {
+        "\u{f071} "
+    } else {
+        ""
+    }
+}
+
 fn render_search(search_query: &SearchQuery, frame: &mut Frame, view: Rect) {
     frame.render_widget(Span::raw(format!("/{}", search_query.to_string())), view);
 }
 
-const fn kind_str(kind: &Issuekind) -> &str {
-    match kind {
-        Issuekind::Improvement => "Improvement",
-        Issuekind::Issue => "Issue",
+/// The colours tags are shown in. Which one a tag gets depends on its name,
+/// so it's the same everywhere
+const TAG_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::LightRed,
+];
+
+/// Every tag as a coloured chip, separated by spaces
+fn tag_chips(tags: &[String]) -> Vec<Span<'static>> {
+    tags.iter()
+        .flat_map(|tag| {
+            let hash = tag
+                .bytes()
+                .fold(0usize, |acc, x| acc.wrapping_mul(31).wrapping_add(x.into()));
+            [
+                Span::styled(
+                    format!(" {tag} "),
+                    Style::new()
+                        .fg(Color::Black)
+                        .bg(TAG_COLORS[hash % TAG_COLORS.len()]),
+                ),
+                Span::raw(" "),
+            ]
+        })
+        .collect()
+}
+
+/// How many people voted for an idea, highlighted if the user is one of them
+fn vote_count(app: &App, votes: &[String], width: usize) -> Span<'static> {
+    let style = if votes.contains(&app.user) {
+        Style::new().fg(Color::Yellow).bold()
+    } else {
+        Style::new().fg(Color::DarkGray)
+    };
+    Span::styled(format!("\u{25b2}{:>width$} ", votes.len()), style)
+}
+
+/// Marks the ideas that aren't of normal priority, so the urgent ones stand
+/// out
+fn priority_marker(priority: Priority) -> Span<'static> {
+    match priority {
+        Priority::Low => Span::styled("\u{2193} ", Style::new().fg(Color::DarkGray)),
+        Priority::Normal => Span::raw("  "),
+        Priority::High => Span::styled("! ", Style::new().fg(Color::Yellow).bold()),
+        Priority::Urgent => Span::styled("\u{203c} ", Style::new().fg(Color::Red).bold()),
     }
 }
+
+/// The icon in front of every idea, telling its status at a glance
+pub(super) fn status_icon(status: Status) -> Span<'static> {
+    let (icon, color) = match status {
+        Status::New => ("\u{f41b}", Color::Green),
+        Status::Triaged => ("\u{f441}", Color::Cyan),
+        Status::InProgress => ("\u{f43a}", Color::Yellow),
+        Status::Resolved => ("\u{f41d}", Color::Magenta),
+        Status::Rejected => ("\u{f468}", Color::Red),
+        Status::Duplicate => ("\u{f4c4}", Color::DarkGray),
+    };
+    Span::styled(format!(" {icon} "), Style::new().fg(color))
+}
@@ -0,0 +1,128 @@
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use ratatui::prelude::*;
+use sea_orm::sqlx::types::chrono;
+
+use crate::app::App;
+
+use super::ideas::status_icon;
+
+pub fn render(app: &App, frame: &mut Frame, mainview: Rect, infoview: Rect) {
+    render_select(app, frame, mainview);
+    render_infoview(app, frame, infoview);
+}
+
+fn render_select(app: &App, frame: &mut Frame, view: Rect) {
+    let meetings = app.view_data.meetings.all();
+    let block = Block::bordered()
+        .title(format!(
+            "Meetings{}",
+            if app.refreshing { " (refreshing)" } else { "" }
+        ))
+        .style(Color::White)
+        .border_type(ratatui::widgets::BorderType::Rounded);
+    if meetings.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No meetings yet, n to plan one")
+                .wrap(Wrap { trim: false })
+                .block(block),
+            view,
+        );
+        return;
+    }
+
+    let now = chrono::Local::now().naive_local();
+    let list = List::new(meetings.iter().map(|x| {
+        // Meetings that were held already are of less interest
+        let time_style = if x.meeting.time < now {
+            Style::new().dark_gray()
+        } else {
+            Style::new().green()
+        };
+        let items = match x.agenda.len() {
+            0 => String::new(),
+            1 => " 1 item".to_string(),
+            n => format!(" {n} items"),
+        };
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", x.meeting.time.format("%d/%m/%Y %H:%M")),
+                time_style,
+            ),
+            Span::raw(x.meeting.title.clone()),
+            Span::styled(items, Style::new().dark_gray()),
+        ])
+    }))
+    .block(block)
+    .scroll_padding(3)
+    .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(app.view_data.meetings.selected);
+    frame.render_stateful_widget(list, view, &mut list_state);
+}
+
+fn render_infoview(app: &App, frame: &mut Frame, view: Rect) {
+    let meetings = &app.view_data.meetings;
+    let Some(meeting) = meetings.current() else {
+        frame.render_widget(
+            Paragraph::new("Select a meeting to view").block(
+                Block::bordered()
+                    .title("Agenda")
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            ),
+            view,
+        );
+        return;
+    };
+
+    let mut lines = vec![
+        Line::raw(meeting.meeting.time.format("%d/%m/%Y %H:%M").to_string()),
+        if meeting.attendees.is_empty() {
+            Line::styled("Nobody is attending yet", Style::new().dark_gray())
+        } else {
+            Line::raw(format!(
+                "Attendees: {}",
+                meeting.attendee_names(&app.view_data.people).join(", ")
+            ))
+        },
+        Line::styled("\u{2500}".repeat(50), Style::new().fg(Color::Green)),
+        Line::raw("Agenda (a to change, Enter to record a decision, x for minutes):").bold(),
+    ];
+    if meeting.agenda.is_empty() {
+        lines.push(Line::styled("Nothing yet", Style::new().dark_gray()));
+    }
+    for (i, item) in meeting.agenda.iter().enumerate() {
+        let Some(idea) = app.view_data.idea.get_any(item.idea) else {
+            continue;
+        };
+        let mut line = Line::from(vec![
+            Span::raw(format!("{}.", i + 1)),
+            status_icon(idea.status),
+            Span::raw(idea.title.clone()),
+        ]);
+        if meetings.item_cursor() == Some(i) {
+            line = line.reversed();
+        }
+        lines.push(line);
+        lines.push(if item.decision.is_empty() {
+            Line::styled("   No decision yet", Style::new().dark_gray())
+        } else {
+            Line::styled(
+                format!("   Decided: {}", item.decision),
+                Style::new().cyan(),
+            )
+        });
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::bordered()
+                .title(meeting.meeting.title.clone())
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        ),
+        view,
+    );
+}
@@ -7,23 +7,77 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
+use crate::{
+    app::{App, View},
+    listener::Status,
+};
 
+mod error_log;
 mod ideas;
+mod meetings;
+mod roster;
 
 /// Draws the ui.
 /// It probably assumes a lot about the
 /// terminal being in raw mode etc.
 pub fn ui(frame: &mut Frame, app: &App) {
+    let outer_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
+        .split(frame.area());
     let main_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(Constraint::from_percentages([40, 60]))
-        .split(frame.area());
-    ideas::render(app, frame, main_layout[0], main_layout[1]);
+        .split(outer_layout[0]);
+    match app.view {
+        View::Roster => roster::render(app, frame, main_layout[0], main_layout[1]),
+        View::Meetings => meetings::render(app, frame, main_layout[0], main_layout[1]),
+        View::Ideas | View::Mine | View::Trash | View::DueSoon => {
+            ideas::render(app, frame, main_layout[0], main_layout[1]);
+        }
+    }
+    render_status_bar(app, frame, outer_layout[1]);
     if let Some(x) = &app.popup {
         let area = centered_rect(70, 80, frame.area());
         x.render(app.style, area, frame);
     }
+    if app.error_log.open {
+        error_log::render(app, frame, centered_rect(70, 60, frame.area()));
+    }
+}
+
+/// The line at the bottom of the screen
+fn render_status_bar(app: &App, frame: &mut Frame, view: Rect) {
+    let listener = match app.listener {
+        None => Span::raw(""),
+        Some(Status::Connecting) => Span::styled("\u{25cc} connecting", Style::new().yellow()),
+        Some(Status::Connected) => Span::styled("\u{25cf} live", Style::new().green()),
+        Some(Status::Disconnected(ref err)) => Span::styled(
+            format!("\u{25cb} live updates disconnected: {err}"),
+            Style::new().red(),
+        ),
+    };
+    let listener_width = u16::try_from(listener.width()).unwrap_or(u16::MAX);
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Length(listener_width)])
+        .split(view);
+
+    if let Some(entry) = app.error_log.toast() {
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled(
+                    format!("{} failed: {} ", entry.operation, entry.error),
+                    Style::new().red(),
+                ),
+                Span::styled("(L to show all errors)", Style::new().dark_gray()),
+            ]),
+            layout[0],
+        );
+    } else if let Some(notice) = app.notice() {
+        frame.render_widget(Span::styled(notice, Style::new().green()), layout[0]);
+    }
+    frame.render_widget(listener, layout[1]);
 }
 
 /// This code is absolutely stolen from the ratatui json example
@@ -0,0 +1,154 @@
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use ratatui::prelude::*;
+
+use crate::app::App;
+
+use super::ideas::status_icon;
+
+pub fn render(app: &App, frame: &mut Frame, mainview: Rect, infoview: Rect) {
+    render_select(app, frame, mainview);
+    render_infoview(app, frame, infoview);
+}
+
+fn render_select(app: &App, frame: &mut Frame, view: Rect) {
+    let roster = &app.view_data.roster;
+    let (total, left) = roster.counts();
+    let block = Block::bordered()
+        .title(format!(
+            "{} ({left} of {total} left, a to {}){}",
+            if roster.show_all {
+                "Roster"
+            } else {
+                "People left to talk to"
+            },
+            if roster.show_all {
+                "hide the ones talked to"
+            } else {
+                "show everyone"
+            },
+            if app.refreshing { " (refreshing)" } else { "" }
+        ))
+        .style(Color::White)
+        .border_type(ratatui::widgets::BorderType::Rounded);
+    if total == 0 {
+        frame.render_widget(
+            Paragraph::new("Nobody is on the roster yet. Import a class list with --import-roster")
+                .wrap(Wrap { trim: false })
+                .block(block),
+            view,
+        );
+        return;
+    }
+
+    let max_class_len = roster
+        .shown()
+        .map(|x| x.roster.class.chars().count())
+        .max()
+        .unwrap_or(0);
+    let list = List::new(roster.shown().map(|entry| {
+        // Everyone shown is left to talk to otherwise
+        let contacted = entry.roster.contacted_at.map_or_else(
+            || Span::raw(if roster.show_all { "        " } else { "" }),
+            |time| {
+                Span::styled(
+                    format!("\u{2713} {} ", time.format("%d/%m")),
+                    Style::new().green(),
+                )
+            },
+        );
+        let ideas = match entry.ideas.len() {
+            0 => String::new(),
+            1 => " 1 idea".to_string(),
+            n => format!(" {n} ideas"),
+        };
+        Line::from(vec![
+            contacted,
+            Span::styled(
+                format!("{:max_class_len$} ", entry.roster.class),
+                Style::new().red(),
+            ),
+            Span::raw(app.view_data.people.name(entry.roster.person).to_string()),
+            Span::styled(ideas, Style::new().dark_gray()),
+        ])
+    }))
+    .block(block)
+    .scroll_padding(3)
+    .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(roster.selected);
+    frame.render_stateful_widget(list, view, &mut list_state);
+}
+
+fn render_infoview(app: &App, frame: &mut Frame, view: Rect) {
+    let Some(entry) = app.view_data.roster.current() else {
+        frame.render_widget(
+            Paragraph::new("Select someone to view").block(
+                Block::bordered()
+                    .title("Conversation")
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            ),
+            view,
+        );
+        return;
+    };
+    let roster = &entry.roster;
+
+    let mut lines = vec![
+        Line::raw(format!("Class: {}", roster.class)),
+        match (roster.contacted_at, &roster.contacted_by) {
+            (Some(time), by) => Line::styled(
+                format!(
+                    "Talked to {}{}",
+                    time.format("%d/%m/%Y %H:%M"),
+                    by.as_ref().map_or(String::new(), |x| format!(" by {x}"))
+                ),
+                Style::new().green(),
+            ),
+            (None, _) => Line::styled(
+                "Nobody has talked to them yet, c to mark them as talked to",
+                Style::new().yellow(),
+            ),
+        },
+        Line::raw(""),
+    ];
+    if roster.notes.is_empty() {
+        lines.push(Line::styled(
+            "No notes, e to write some",
+            Style::new().dark_gray(),
+        ));
+    } else {
+        lines.extend(roster.notes.lines().map(Line::raw));
+    }
+    lines.push(Line::styled(
+        "\u{2500}".repeat(50),
+        Style::new().fg(Color::Green),
+    ));
+    lines.push(Line::raw("Ideas that came out of it (l to link):").bold());
+    let ideas = entry
+        .ideas
+        .iter()
+        .filter_map(|&id| app.view_data.idea.get(id))
+        .collect::<Vec<_>>();
+    if ideas.is_empty() {
+        lines.push(Line::styled("None yet", Style::new().dark_gray()));
+    }
+    for idea in ideas {
+        lines.push(Line::from(vec![
+            status_icon(idea.status),
+            Span::raw(idea.title.clone()),
+        ]));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::bordered()
+                .title(app.view_data.people.name(roster.person).to_string())
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        ),
+        view,
+    );
+}
@@ -1,15 +1,15 @@
+use std::cell::Cell;
+
+/// Hands out the ids used to keep track of db actions. It is shared between
+/// everything that creates them, so the ids are unique across all of them
 #[derive(Debug, Default)]
 pub struct Counter {
-    counter: usize,
+    counter: Cell<usize>,
 }
 
 impl Counter {
-    pub fn next(&mut self) -> usize {
-        self.counter += 1;
-        self.counter
-    }
-
-    pub const fn get(&self) -> usize {
-        self.counter
+    pub fn next(&self) -> usize {
+        self.counter.set(self.counter.get() + 1);
+        self.counter.get()
     }
 }
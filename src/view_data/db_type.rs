@@ -7,6 +7,9 @@ where
     InDb(T),
     /// For futures that are currently awaited to be pushed
     DbActionPending(usize, T),
+    /// For actions that failed, together with the error. They stay around
+    /// until they are either retried or rolled back
+    Failed(usize, T, String),
 }
 
 impl<T> DbType<T>
@@ -15,20 +18,35 @@ where
 {
     pub const fn get_entry(&self) -> &T {
         match self {
-            Self::InDb(ref x) | Self::DbActionPending(_, ref x) => x,
+            Self::InDb(ref x) | Self::DbActionPending(_, ref x) | Self::Failed(_, ref x, _) => x,
         }
     }
 
     pub fn get_entry_mut(&mut self) -> &mut T {
         match self {
-            Self::InDb(ref mut x) | Self::DbActionPending(_, ref mut x) => x,
+            Self::InDb(ref mut x)
+            | Self::DbActionPending(_, ref mut x)
+            | Self::Failed(_, ref mut x, _) => x,
         }
     }
 
+    /// The id of the action affecting this entry, if there is one
+    pub const fn action_id(&self) -> Option<usize> {
+        match self {
+            Self::InDb(_) => None,
+            Self::DbActionPending(id, _) | Self::Failed(id, _, _) => Some(*id),
+        }
+    }
+
+    /// Whether the entry has local changes that aren't in the db (yet)
+    pub const fn is_local(&self) -> bool {
+        !matches!(self, Self::InDb(_))
+    }
+
     /// Converts self to a database entry.
     /// This happens unchecked and the id associated with it will be forgotten
     pub fn convert_to_db(&mut self) {
-        if let Self::DbActionPending(_, x) = self {
+        if let Self::DbActionPending(_, x) | Self::Failed(_, x, _) = self {
             *self = Self::InDb(x.clone());
         }
     }
@@ -45,4 +63,27 @@ where
             Err(())
         }
     }
+
+    /// Marks the pending action `id` as failed. Returns whether it matched
+    pub fn fail(&mut self, id: usize, error: &str) -> bool {
+        match self {
+            Self::DbActionPending(action_id, x) if *action_id == id => {
+                *self = Self::Failed(id, x.clone(), error.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Marks the failed action `id` as pending again. Returns whether it
+    /// matched
+    pub fn retry(&mut self, id: usize) -> bool {
+        match self {
+            Self::Failed(action_id, x, _) if *action_id == id => {
+                *self = Self::DbActionPending(id, x.clone());
+                true
+            }
+            _ => false,
+        }
+    }
 }
@@ -1,67 +1,726 @@
-use core::panic;
 use crossterm::event::KeyEvent;
 use futures::FutureExt;
-use sea_orm::{ColumnTrait, ConnectOptions, Database, DbErr, EntityTrait, QueryFilter, QueryOrder};
-use std::{cmp, sync::Arc};
+use sea_orm::{
+    prelude::{Date, DateTime},
+    sqlx::types::chrono,
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
+};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    mem,
+    rc::Rc,
+    vec,
+};
 
 use crate::{
-    app::DbActionReturn,
+    app::{DbActionParts, DbActionReturn},
     entities::{
-        comment,
+        assignee, comment, comment_revision,
         idea::{self},
-        prelude::{Comment as eComment, Idea as eIdea},
+        idea_revision, idea_tag, person,
+        prelude::{
+            Assignee as eAssignee, Comment as eComment, CommentRevision as eCommentRevision,
+            Idea as eIdea, IdeaIdentity as eIdeaIdentity, IdeaRevision as eIdeaRevision,
+            IdeaTag as eIdeaTag, StatusHistory as eStatusHistory, Tag as eTag, Vote as eVote,
+        },
+        roster,
+        sea_orm_active_enums::Status,
+        status_history, tag, vote,
     },
 };
 
-use super::{counter::Counter, db_type::DbType, search_query::SearchQuery, ViewData};
+use super::{
+    counter::Counter, db_type::DbType, meeting::Meeting, search_query::SearchQuery, ViewData,
+};
+
+/// An idea in the list, with everything shown alongside it
+#[derive(Debug, Clone)]
+pub struct IdeaEntry {
+    /// The idea description
+    pub idea: DbType<idea::Model>,
+    /// The comments on the idea
+    pub comments: Vec<DbType<comment::Model>>,
+    /// Scroll distance
+    pub scroll: u16,
+    /// The names of the tags on the idea, sorted
+    pub tags: Vec<String>,
+    /// Who voted for the idea, sorted
+    pub voters: Vec<String>,
+    /// Who is responsible for following the idea up, sorted
+    pub assignees: Vec<String>,
+}
+
+impl IdeaEntry {
+    /// An entry for `idea` with nothing alongside it yet
+    const fn new(idea: DbType<idea::Model>) -> Self {
+        Self {
+            idea,
+            comments: Vec::new(),
+            scroll: 0,
+            tags: Vec::new(),
+            voters: Vec::new(),
+            assignees: Vec::new(),
+        }
+    }
+}
+
+/// An idea with its comments, tags, voters and assignees as loaded by
+/// [`Idea::fetch`]
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    pub idea: idea::Model,
+    pub comments: Vec<comment::Model>,
+    pub tags: Vec<String>,
+    pub voters: Vec<String>,
+    pub assignees: Vec<String>,
+}
 
-/// 0: The idea description
-/// 1: The comments on the idea
-/// 2: Scroll distance
-pub type IdeaType = (DbType<idea::Model>, Vec<DbType<comment::Model>>, u16);
+/// All ideas as loaded by [`Idea::fetch`], oldest first
+pub type Snapshot = Vec<Fetched>;
+
+/// A single change to the db made by someone else
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+#[derive(Debug)]
+pub enum Change {
+    /// The idea was inserted or updated
+    Idea(idea::Model),
+    /// The idea with the id was deleted
+    IdeaDeleted(i32),
+    /// The comment was inserted or updated
+    Comment(comment::Model),
+    /// The comment with the id was deleted
+    CommentDeleted(i32),
+    /// The tags on the idea with the id changed
+    Tags(i32, Vec<String>),
+    /// Someone voted for the idea with the id, or took their vote back
+    Votes(i32, Vec<String>),
+    /// Someone was assigned to the idea with the id, or unassigned
+    Assignees(i32, Vec<String>),
+    /// The person was added or renamed
+    Person(person::Model),
+    /// The person with the id was deleted, after being merged into someone
+    PersonDeleted(i32),
+    /// The person was put on the roster, or their entry changed
+    Roster(roster::Model),
+    /// The person with the id was taken off the roster
+    RosterDeleted(i32),
+    /// The ideas that came out of talking to the person with the id changed
+    RosterIdeas(i32, Vec<i32>),
+    /// The meeting, its attendees or its agenda changed
+    Meeting(Meeting),
+    /// The meeting with the id was deleted
+    MeetingDeleted(i32),
+}
+
+/// A comment as shown in the conversation below an idea
+#[derive(Debug)]
+pub struct ThreadEntry<'a> {
+    pub comment: &'a DbType<comment::Model>,
+    /// How many replies deep the comment is
+    pub depth: usize,
+    /// How many replies are hidden because the comment is collapsed
+    pub hidden: usize,
+}
+
+/// The order the ideas are listed in. The first ones are shown at the top
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    /// Newest first
+    #[default]
+    Time,
+    /// Most votes first
+    Votes,
+    /// Most urgent first
+    Priority,
+}
+
+impl Sort {
+    /// The next order to sort by
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Time => Self::Votes,
+            Self::Votes => Self::Priority,
+            Self::Priority => Self::Time,
+        }
+    }
+
+    /// How the order is shown to the user
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Time => "newest",
+            Self::Votes => "votes",
+            Self::Priority => "priority",
+        }
+    }
+}
+
+/// Identifies an idea across refreshes, even before the db has given it an id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Db(i32),
+    Pending(usize),
+}
+
+impl Key {
+    fn of(entry: &DbType<idea::Model>) -> Self {
+        match entry {
+            DbType::DbActionPending(action_id, x) | DbType::Failed(action_id, x, _)
+                if x.id == -1 =>
+            {
+                Self::Pending(*action_id)
+            }
+            x => Self::Db(x.get_entry().id),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Idea {
     pub selected: Option<usize>,
-    ideas: Vec<IdeaType>,
+    ideas: Vec<IdeaEntry>,
     pub search_query: Option<SearchQuery>,
-    counter: Arc<Counter>,
+    /// Only the ideas assigned to this person are shown, if set
+    assigned_to: Option<String>,
+    /// Whether the ideas in the trash are shown, instead of the others
+    trash: bool,
+    /// Only the open ideas due on this day or earlier are shown, soonest
+    /// first, if set
+    due_by: Option<Date>,
+    sort: Sort,
+    /// The position of the selected comment in the [`Self::thread`] of the
+    /// selected idea
+    comment_cursor: Option<usize>,
+    /// The ids of the comments whose replies are hidden
+    collapsed: HashSet<i32>,
+    counter: Rc<Counter>,
+    /// The ids of the actions deleting an idea. Those ideas are hidden while
+    /// the action is pending
+    deleting: HashSet<usize>,
+    /// The ids of the ideas and comments inserted since the last refresh. A
+    /// snapshot fetched before they were doesn't have them, but they aren't
+    /// gone
+    inserted: HashSet<i32>,
+    inserted_comments: HashSet<i32>,
 }
 
 impl Idea {
-    pub async fn new(conn_opts: &ConnectOptions, counter: Arc<Counter>) -> Result<Self, DbErr> {
-        let db = Database::connect(conn_opts.clone()).await?;
+    pub async fn new(db: &DatabaseConnection, counter: Rc<Counter>) -> Result<Self, DbErr> {
+        let mut ret = Self {
+            ideas: Vec::new(),
+            counter,
+            selected: None,
+            search_query: None,
+            assigned_to: None,
+            trash: false,
+            due_by: None,
+            sort: Sort::default(),
+            comment_cursor: None,
+            collapsed: HashSet::new(),
+            deleting: HashSet::new(),
+            inserted: HashSet::new(),
+            inserted_comments: HashSet::new(),
+        };
+        ret.refresh(Self::fetch(db).await?);
+        Ok(ret)
+    }
+
+    /// Loads all ideas and their comments, tags, voters and assignees, oldest
+    /// first
+    pub async fn fetch(db: &DatabaseConnection) -> Result<Snapshot, DbErr> {
         let ideas = eIdea::find()
             .find_with_related(comment::Entity)
             .order_by_asc(idea::Column::Time)
-            .all(&db)
+            .all(db)
+            .await?;
+        let mut tags = HashMap::<_, Vec<_>>::new();
+        for (link, tag) in eIdeaTag::find().find_also_related(eTag).all(db).await? {
+            if let Some(tag) = tag {
+                tags.entry(link.idea).or_default().push(tag.name);
+            }
+        }
+        let mut votes = HashMap::<_, Vec<_>>::new();
+        for vote in eVote::find().all(db).await? {
+            votes.entry(vote.idea).or_default().push(vote.voter);
+        }
+        let mut assignees = HashMap::<_, Vec<_>>::new();
+        for assignee in eAssignee::find().all(db).await? {
+            assignees
+                .entry(assignee.idea)
+                .or_default()
+                .push(assignee.name);
+        }
+
+        Ok(ideas
+            .into_iter()
+            .map(|(idea, mut comments)| {
+                comments.sort_by_key(|x| x.time);
+                let mut tags = tags.remove(&idea.id).unwrap_or_default();
+                tags.sort();
+                let mut voters = votes.remove(&idea.id).unwrap_or_default();
+                voters.sort();
+                let mut assignees = assignees.remove(&idea.id).unwrap_or_default();
+                assignees.sort();
+                Fetched {
+                    idea,
+                    comments,
+                    tags,
+                    voters,
+                    assignees,
+                }
+            })
+            .collect())
+    }
+
+    /// Loads who voted for the idea with the id, sorted
+    pub async fn fetch_votes<C>(db: &C, id: i32) -> Result<Vec<String>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Ok(eVote::find()
+            .filter(vote::Column::Idea.eq(id))
+            .order_by_asc(vote::Column::Voter)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|x| x.voter)
+            .collect())
+    }
+
+    /// Loads the versions of the idea with the id that were replaced by
+    /// edits, oldest first
+    pub async fn fetch_revisions<C>(db: &C, id: i32) -> Result<Vec<idea_revision::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        eIdeaRevision::find()
+            .filter(idea_revision::Column::Idea.eq(id))
+            .order_by_asc(idea_revision::Column::Time)
+            .order_by_asc(idea_revision::Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// Loads the changes of the status of the idea with the id, oldest first
+    pub async fn fetch_status_history<C>(
+        db: &C,
+        id: i32,
+    ) -> Result<Vec<status_history::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        eStatusHistory::find()
+            .filter(status_history::Column::Idea.eq(id))
+            .order_by_asc(status_history::Column::Time)
+            .order_by_asc(status_history::Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// Loads the versions of the comment with the id that were replaced by
+    /// edits, oldest first
+    pub async fn fetch_comment_revisions<C>(
+        db: &C,
+        id: i32,
+    ) -> Result<Vec<comment_revision::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        eCommentRevision::find()
+            .filter(comment_revision::Column::Comment.eq(id))
+            .order_by_asc(comment_revision::Column::Time)
+            .order_by_asc(comment_revision::Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// Loads who really submitted the anonymous idea with the id. `None` if
+    /// they didn't give their name
+    pub async fn fetch_identity<C>(db: &C, id: i32) -> Result<Option<String>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Ok(eIdeaIdentity::find_by_id(id)
+            .one(db)
+            .await?
+            .map(|x| x.author))
+    }
+
+    /// Loads who is assigned to the idea with the id, sorted
+    pub async fn fetch_assignees<C>(db: &C, id: i32) -> Result<Vec<String>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Ok(eAssignee::find()
+            .filter(assignee::Column::Idea.eq(id))
+            .order_by_asc(assignee::Column::Name)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|x| x.name)
+            .collect())
+    }
+
+    /// Loads the names of the tags on the idea with the id, sorted
+    pub async fn fetch_tags<C>(db: &C, id: i32) -> Result<Vec<String>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Ok(eTag::find()
+            .join(sea_orm::JoinType::InnerJoin, tag::Relation::IdeaTag.def())
+            .filter(idea_tag::Column::Idea.eq(id))
+            .order_by_asc(tag::Column::Name)
+            .all(db)
             .await?
             .into_iter()
-            .map(|(a, b)| {
-                let mut b: Vec<_> = b.into_iter().map(DbType::InDb).collect();
-                b.sort_by_key(|x| x.get_entry().time);
-                (DbType::InDb(a), b, 0)
+            .map(|x| x.name)
+            .collect())
+    }
+
+    /// Replaces the ideas with a newly fetched `snapshot`.
+    ///
+    /// Entries with a db action in flight or a failed one are kept as they
+    /// are, as they are newer than what's in the snapshot. So are the ones
+    /// inserted since the last refresh, which the snapshot may predate. The selection and the scroll
+    /// offsets stay with the idea they belonged to
+    pub fn refresh(&mut self, snapshot: Snapshot) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        let mut old = mem::take(&mut self.ideas);
+        let inserted = mem::take(&mut self.inserted);
+        let inserted_comments = mem::take(&mut self.inserted_comments);
+
+        for fetched in snapshot {
+            let prev = old
+                .iter()
+                .position(|x| x.idea.get_entry().id == fetched.idea.id)
+                .map(|pos| old.remove(pos));
+            let mut entry = match prev {
+                Some(prev) if prev.idea.is_local() => prev,
+                Some(prev) => IdeaEntry {
+                    idea: DbType::InDb(fetched.idea),
+                    comments: prev.comments,
+                    scroll: prev.scroll,
+                    tags: fetched.tags,
+                    voters: fetched.voters,
+                    assignees: fetched.assignees,
+                },
+                None => IdeaEntry {
+                    tags: fetched.tags,
+                    voters: fetched.voters,
+                    assignees: fetched.assignees,
+                    ..IdeaEntry::new(DbType::InDb(fetched.idea))
+                },
+            };
+
+            let mut old_comments = mem::take(&mut entry.comments);
+            entry.comments = fetched
+                .comments
+                .into_iter()
+                .map(|comment| {
+                    old_comments
+                        .iter()
+                        .position(|x| x.is_local() && x.get_entry().id == comment.id)
+                        .map_or(DbType::InDb(comment), |pos| old_comments.remove(pos))
+                })
+                .collect();
+            entry.comments.extend(
+                old_comments
+                    .into_iter()
+                    .filter(|x| x.is_local() || inserted_comments.contains(&x.get_entry().id)),
+            );
+            self.ideas.push(entry);
+        }
+
+        // Whatever is left has either been deleted by someone else, has not
+        // made it into the db yet, or was inserted after the snapshot was
+        // fetched
+        self.ideas.extend(
+            old.into_iter()
+                .filter(|x| x.idea.is_local() || inserted.contains(&x.idea.get_entry().id)),
+        );
+        self.reselect(selected);
+    }
+
+    /// Applies a single change made by someone else. Like with
+    /// [`Self::refresh`], entries with local changes are left alone
+    pub fn apply(&mut self, change: Change) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        match change {
+            Change::Idea(idea) => {
+                if let Some(entry) = self
+                    .ideas
+                    .iter_mut()
+                    .find(|x| x.idea.get_entry().id == idea.id)
+                {
+                    if let DbType::InDb(ref mut x) = entry.idea {
+                        *x = idea;
+                    }
+                } else {
+                    let pos = self
+                        .ideas
+                        .iter()
+                        .position(|x| x.idea.get_entry().time > idea.time)
+                        .unwrap_or(self.ideas.len());
+                    self.ideas.insert(pos, IdeaEntry::new(DbType::InDb(idea)));
+                }
+            }
+            Change::IdeaDeleted(id) => self
+                .ideas
+                .retain(|x| !matches!(x.idea, DbType::InDb(ref x) if x.id == id)),
+            Change::Comment(comment) => {
+                if let Some(entry) = self
+                    .ideas
+                    .iter_mut()
+                    .find(|x| x.idea.get_entry().id == comment.comments_on)
+                {
+                    if let Some(x) = entry
+                        .comments
+                        .iter_mut()
+                        .find(|x| x.get_entry().id == comment.id)
+                    {
+                        if let DbType::InDb(ref mut x) = x {
+                            *x = comment;
+                        }
+                    } else {
+                        entry.comments.push(DbType::InDb(comment));
+                    }
+                }
+            }
+            Change::CommentDeleted(id) => {
+                for entry in &mut self.ideas {
+                    entry
+                        .comments
+                        .retain(|x| !matches!(x, DbType::InDb(x) if x.id == id));
+                }
+            }
+            Change::Tags(id, tags) => {
+                if let Some(entry) = self
+                    .ideas
+                    .iter_mut()
+                    .find(|x| matches!(x.idea, DbType::InDb(ref x) if x.id == id))
+                {
+                    entry.tags = tags;
+                }
+            }
+            Change::Votes(id, votes) => {
+                if let Some(entry) = self
+                    .ideas
+                    .iter_mut()
+                    .find(|x| matches!(x.idea, DbType::InDb(ref x) if x.id == id))
+                {
+                    entry.voters = votes;
+                }
+            }
+            Change::Assignees(id, assignees) => {
+                if let Some(entry) = self
+                    .ideas
+                    .iter_mut()
+                    .find(|x| matches!(x.idea, DbType::InDb(ref x) if x.id == id))
+                {
+                    entry.assignees = assignees;
+                }
+            }
+            // The people and the roster are kept by the view data
+            Change::Person(_)
+            | Change::PersonDeleted(_)
+            | Change::Roster(_)
+            | Change::RosterDeleted(_)
+            | Change::RosterIdeas(..)
+            | Change::Meeting(_)
+            | Change::MeetingDeleted(_) => (),
+        }
+        self.reselect(selected);
+    }
+
+    /// Makes everything written by the person with the id `from` written by
+    /// `into`
+    pub fn replace_author(&mut self, from: i32, into: i32) {
+        for entry in &mut self.ideas {
+            let idea = entry.idea.get_entry_mut();
+            if idea.author_id == Some(from) {
+                idea.author_id = Some(into);
+            }
+            for comment in &mut entry.comments {
+                let comment = comment.get_entry_mut();
+                if comment.author_id == from {
+                    comment.author_id = into;
+                }
+            }
+        }
+    }
+
+    /// Moves the selection to the idea identified by `key`, keeping the
+    /// index within bounds if it's gone
+    fn reselect(&mut self, key: Option<Key>) {
+        let amount = self.filtered_ideas().count();
+        if amount == 0 {
+            self.selected = None;
+            return;
+        }
+        if let Some(pos) =
+            key.and_then(|key| self.filtered_ideas().position(|x| Key::of(&x.idea) == key))
+        {
+            self.selected = Some(pos);
+        } else if let Some(ref mut selected) = self.selected {
+            *selected = cmp::min(*selected, amount - 1);
+        }
+    }
+
+    /// The indices into `ideas` of the ideas matching the search query.
+    /// Ideas that are being deleted are left out
+    fn filtered_indices(&self) -> vec::IntoIter<usize> {
+        let mut indices = self
+            .ideas
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| {
+                !matches!(x.idea, DbType::DbActionPending(id, _) if self.deleting.contains(&id))
             })
-            .collect();
-        Ok(Self {
-            ideas,
-            counter,
-            selected: None,
-            search_query: None,
-        })
+            .filter(|(_, x)| x.idea.get_entry().deleted_at.is_some() == self.trash)
+            .filter(|(_, x)| {
+                self.assigned_to
+                    .as_ref()
+                    .is_none_or(|assignee| x.assignees.contains(assignee))
+            })
+            .filter(|(_, x)| {
+                self.due_by.is_none_or(|by| {
+                    let idea = x.idea.get_entry();
+                    idea.status.is_open() && idea.due.is_some_and(|due| due.date() <= by)
+                })
+            })
+            .filter(|(_, x)| {
+                self.search_query
+                    .as_ref()
+                    .is_none_or(|search_query| search_query.matches(x))
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        // The ideas are stored oldest first, and shown in reverse
+        match self.sort {
+            Sort::Time => (),
+            Sort::Votes => indices.sort_by_key(|&i| self.ideas[i].voters.len()),
+            Sort::Priority => indices.sort_by_key(|&i| self.ideas[i].idea.get_entry().priority),
+        }
+        if self.due_by.is_some() {
+            indices.sort_by_key(|&i| cmp::Reverse(self.ideas[i].idea.get_entry().due));
+        }
+        indices.into_iter()
     }
 
-    pub fn filtered_ideas(&self) -> impl DoubleEndedIterator<Item = &idea::Model> + Clone {
-        self.ideas.iter().map(|x| x.0.get_entry()).filter(|x| {
-            self.search_query.as_ref().map_or(true, |search_query| {
-                x.title
-                    .to_lowercase()
-                    .starts_with(&search_query.to_string().to_lowercase())
+    pub const fn sort(&self) -> Sort {
+        self.sort
+    }
+
+    /// Shows the ideas in the trash instead of the others
+    pub fn set_trash(&mut self, trash: bool) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        self.trash = trash;
+        self.reselect(selected);
+    }
+
+    /// Only shows the open ideas due on the day `by` or earlier, or all of
+    /// them if it's `None`
+    pub fn set_due_by(&mut self, by: Option<Date>) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        self.due_by = by;
+        self.reselect(selected);
+    }
+
+    /// The open ideas that became overdue after `since`, up to `today`,
+    /// soonest due first
+    pub fn overdue_since(&self, since: DateTime, today: Date) -> Vec<&idea::Model> {
+        let mut overdue = self
+            .ideas
+            .iter()
+            .map(|x| x.idea.get_entry())
+            .filter(|idea| {
+                idea.is_overdue(today) && idea.due.is_some_and(|due| due.date() >= since.date())
             })
+            .collect::<Vec<_>>();
+        overdue.sort_by_key(|x| x.due);
+        overdue
+    }
+
+    /// Only shows the ideas assigned to `assignee`, or all of them if it's
+    /// `None`
+    pub fn set_assigned_to(&mut self, assignee: Option<String>) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        self.assigned_to = assignee;
+        self.reselect(selected);
+    }
+
+    /// Everyone who is assigned to an idea, sorted
+    pub fn assignees(&self) -> Vec<String> {
+        let mut assignees = self
+            .ideas
+            .iter()
+            .flat_map(|x| x.assignees.iter().cloned())
+            .collect::<Vec<_>>();
+        assignees.sort();
+        assignees.dedup();
+        assignees
+    }
+
+    /// The idea with the id, if it's in the db and not in the trash
+    pub fn get(&self, id: i32) -> Option<&idea::Model> {
+        self.ideas.iter().find_map(|x| match x.idea {
+            DbType::InDb(ref idea) if idea.id == id && idea.deleted_at.is_none() => Some(idea),
+            _ => None,
+        })
+    }
+
+    /// The idea with the id if it's in the db, even if it's in the trash
+    pub fn get_any(&self, id: i32) -> Option<&idea::Model> {
+        self.ideas.iter().find_map(|x| match x.idea {
+            DbType::InDb(ref idea) if idea.id == id => Some(idea),
+            _ => None,
         })
     }
 
+    /// The id and title of every idea that can be linked to, newest first
+    pub fn titles(&self) -> Vec<(i32, String)> {
+        self.ideas
+            .iter()
+            .rev()
+            .filter_map(|x| match x.idea {
+                DbType::InDb(ref idea) if idea.deleted_at.is_none() => {
+                    Some((idea.id, idea.title.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// How many open ideas everyone has assigned, most first
+    pub fn open_per_assignee(&self) -> Vec<(String, usize)> {
+        let mut counts = HashMap::<_, usize>::new();
+        for entry in self.ideas.iter().filter(|x| {
+            let idea = x.idea.get_entry();
+            idea.status.is_open() && idea.deleted_at.is_none()
+        }) {
+            for assignee in &entry.assignees {
+                *counts.entry(assignee.clone()).or_default() += 1;
+            }
+        }
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Switches to the next order, keeping the selected idea selected
+    pub fn next_sort(&mut self) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        self.sort = self.sort.next();
+        self.reselect(selected);
+    }
+
+    pub fn filtered_ideas(&self) -> impl DoubleEndedIterator<Item = &IdeaEntry> + Clone {
+        self.filtered_indices().map(|i| &self.ideas[i])
+    }
+
     pub fn up(&mut self) {
+        self.comment_cursor = None;
         if self.filtered_ideas().next().is_none() {
             self.selected = None;
         } else {
@@ -74,6 +733,7 @@ impl Idea {
     }
 
     pub fn down(&mut self) {
+        self.comment_cursor = None;
         if self.filtered_ideas().next().is_none() {
             self.selected = None;
         } else {
@@ -87,128 +747,657 @@ impl Idea {
         }
     }
 
-    pub fn edit_idea(&mut self, idea: &idea::Model) -> Option<usize> {
-        let Some(counter) = Arc::get_mut(&mut self.counter) else {
-            panic!("I don't even know how.")
+    /// The comments in conversation order: every comment is followed by its
+    /// replies, oldest first. Replies to collapsed comments are left out
+    pub fn thread<'a>(&self, comments: &'a [DbType<comment::Model>]) -> Vec<ThreadEntry<'a>> {
+        let ids = comments
+            .iter()
+            .map(|x| x.get_entry().id)
+            .collect::<HashSet<_>>();
+        let mut replies = HashMap::<_, Vec<_>>::new();
+        let mut roots = Vec::new();
+        for comment in comments {
+            match comment.get_entry().parent_id {
+                Some(parent) if ids.contains(&parent) => {
+                    replies.entry(parent).or_default().push(comment);
+                }
+                _ => roots.push(comment),
+            }
+        }
+
+        fn count(replies: &HashMap<i32, Vec<&DbType<comment::Model>>>, id: i32) -> usize {
+            replies.get(&id).map_or(0, |x| {
+                x.iter().map(|x| 1 + count(replies, x.get_entry().id)).sum()
+            })
+        }
+
+        let mut thread = Vec::new();
+        let mut stack = roots.into_iter().rev().map(|x| (x, 0)).collect::<Vec<_>>();
+        while let Some((comment, depth)) = stack.pop() {
+            // Comments that are being deleted take their replies with them
+            if matches!(comment, DbType::DbActionPending(action_id, _) if self.deleting.contains(action_id))
+            {
+                continue;
+            }
+            let id = comment.get_entry().id;
+            // Pending comments all have the id -1, so nothing replies to them
+            let collapsed = id != -1 && self.collapsed.contains(&id);
+            thread.push(ThreadEntry {
+                comment,
+                depth,
+                hidden: if collapsed { count(&replies, id) } else { 0 },
+            });
+            if !collapsed && id != -1 {
+                if let Some(replies) = replies.get(&id) {
+                    stack.extend(replies.iter().rev().map(|x| (*x, depth + 1)));
+                }
+            }
+        }
+        thread
+    }
+
+    /// The position of the selected comment in the thread of the selected
+    /// idea
+    pub fn comment_cursor(&self) -> Option<usize> {
+        let len = self.thread(&self.current()?.comments).len();
+        self.comment_cursor.filter(|x| *x < len)
+    }
+
+    /// The selected comment of the selected idea
+    pub fn current_comment(&self) -> Option<&comment::Model> {
+        let cursor = self.comment_cursor()?;
+        Some(
+            self.thread(&self.current()?.comments)[cursor]
+                .comment
+                .get_entry(),
+        )
+    }
+
+    /// Moves the comment cursor down the conversation, wrapping around
+    pub fn next_comment(&mut self) {
+        let Some(len) = self.current().map(|x| self.thread(&x.comments).len()) else {
+            return;
+        };
+        self.comment_cursor = match self.comment_cursor() {
+            _ if len == 0 => None,
+            Some(x) => Some((x + 1) % len),
+            None => Some(0),
         };
+    }
 
+    /// Moves the comment cursor up the conversation, wrapping around
+    pub fn prev_comment(&mut self) {
+        let Some(len) = self.current().map(|x| self.thread(&x.comments).len()) else {
+            return;
+        };
+        self.comment_cursor = match self.comment_cursor() {
+            _ if len == 0 => None,
+            Some(x) => Some((x + len - 1) % len),
+            None => Some(len - 1),
+        };
+    }
+
+    /// Hides the replies to the selected comment, or shows them again
+    pub fn toggle_collapsed(&mut self) {
+        let Some(id) = self.current_comment().map(|x| x.id) else {
+            return;
+        };
+        if !self.collapsed.remove(&id) {
+            self.collapsed.insert(id);
+        }
+    }
+
+    /// Applies `edit` to the idea with the id. Returns the action id and what
+    /// the idea looked like before, for rolling back
+    pub fn edit_idea<E>(&mut self, id: i32, edit: E) -> Option<(usize, idea::Model)>
+    where
+        E: FnOnce(&mut idea::Model),
+    {
         let entry = self
             .ideas
             .iter_mut()
-            .find(|x| x.0.get_entry().id == idea.id)?;
-        if entry.0.convert_to_db_action(counter.next()).is_err() {
+            .find(|x| x.idea.get_entry().id == id)?;
+        let action_id = self.counter.next();
+        if entry.idea.convert_to_db_action(action_id).is_err() {
             return None;
         }
-        Some(self.counter.get())
+        let previous = entry.idea.get_entry().clone();
+        edit(entry.idea.get_entry_mut());
+        Some((action_id, previous))
     }
 
-    pub fn new_idea(&mut self, idea: idea::Model) -> usize {
-        let Some(counter) = Arc::get_mut(&mut self.counter) else {
-            panic!("I don't even know how.")
-        };
-        self.ideas
-            .push((DbType::new_future(counter.next(), idea), Vec::new(), 0));
-        self.counter.get()
+    /// Applies `edit` to the comment with the id. Returns the action id and
+    /// what the comment looked like before, for rolling back
+    pub fn edit_comment<E>(&mut self, id: i32, edit: E) -> Option<(usize, comment::Model)>
+    where
+        E: FnOnce(&mut comment::Model),
+    {
+        let comment = self
+            .ideas
+            .iter_mut()
+            .flat_map(|x| x.comments.iter_mut())
+            .find(|x| x.get_entry().id == id)?;
+        let action_id = self.counter.next();
+        if comment.convert_to_db_action(action_id).is_err() {
+            return None;
+        }
+        let previous = comment.get_entry().clone();
+        edit(comment.get_entry_mut());
+        Some((action_id, previous))
     }
 
-    pub fn new_comment(&mut self, comments_on: usize, comment: comment::Model) -> usize {
-        let Some(counter) = Arc::get_mut(&mut self.counter) else {
-            panic!("I don't even know how.")
-        };
+    /// Replaces the tags on the idea with the id. Returns the previous ones,
+    /// for rolling back
+    pub fn set_tags(&mut self, id: i32, tags: Vec<String>) -> Option<Vec<String>> {
+        let entry = self
+            .ideas
+            .iter_mut()
+            .find(|x| x.idea.get_entry().id == id)?;
+        Some(mem::replace(&mut entry.tags, tags))
+    }
 
-        self.ideas[comments_on]
-            .1
-            .push(DbType::new_future(counter.next(), comment));
+    /// Replaces who is assigned to the idea with the id. Returns who was
+    /// assigned before
+    pub fn set_assignees(&mut self, id: i32, assignees: Vec<String>) -> Option<Vec<String>> {
+        let entry = self
+            .ideas
+            .iter_mut()
+            .find(|x| x.idea.get_entry().id == id)?;
+        Some(mem::replace(&mut entry.assignees, assignees))
+    }
+
+    pub fn new_idea(&mut self, idea: idea::Model, tags: Vec<String>) -> usize {
+        let action_id = self.counter.next();
+        self.ideas.push(IdeaEntry {
+            tags,
+            ..IdeaEntry::new(DbType::new_future(action_id, idea))
+        });
+        action_id
+    }
+
+    /// Adds a comment to the idea it `comments_on`. Returns `None` if that
+    /// idea isn't loaded
+    pub fn new_comment(&mut self, comment: comment::Model) -> Option<usize> {
+        let action_id = self.counter.next();
+        self.ideas
+            .iter_mut()
+            .find(|x| x.idea.get_entry().id == comment.comments_on)?
+            .comments
+            .push(DbType::new_future(action_id, comment));
 
-        self.counter.get()
+        Some(action_id)
     }
 
     /// Converts a pendic Db-action to to a DB element by id
     pub fn completed<C>(&mut self, id: usize, callback: C) -> Result<(), ()>
     where
-        C: FnOnce(&mut IdeaType),
+        C: FnOnce(&mut IdeaEntry),
     {
-        let Some(x) = self
+        let Some(pos) = self
             .ideas
-            .iter_mut()
-            .find(|x| matches!(x.0, DbType::DbActionPending(dbid, _) if dbid == id))
+            .iter()
+            .position(|x| matches!(x.idea, DbType::DbActionPending(dbid, _) if dbid == id))
         else {
             return Err(());
         };
+        let x = &mut self.ideas[pos];
+        let old_id = x.idea.get_entry().id;
         callback(x);
-        x.0.convert_to_db();
+        x.idea.convert_to_db();
+        let new_id = x.idea.get_entry().id;
+        if new_id != old_id {
+            self.inserted.insert(new_id);
+        }
+
+        // A refresh might already have loaded the entry from the db
+        let key = Key::of(&self.ideas[pos].idea);
+        if let Some(duplicate) = self
+            .ideas
+            .iter()
+            .enumerate()
+            .position(|(i, x)| i != pos && Key::of(&x.idea) == key)
+        {
+            let selected = self.current().map(|x| Key::of(&x.idea));
+            self.ideas.remove(duplicate);
+            self.reselect(selected);
+        }
 
         Ok(())
     }
 
-    #[allow(clippy::needless_pass_by_ref_mut, clippy::unused_async)]
-    pub async fn refresh(&mut self, _conn_opts: &ConnectOptions) {
-        todo!("This should refresh the Databases inside of here")
+    /// Converts a pending Db-action on a comment to a DB element by id
+    pub fn completed_comment<C>(&mut self, id: usize, callback: C) -> Result<(), ()>
+    where
+        C: FnOnce(&mut comment::Model),
+    {
+        let Some((comments, pos)) = self.ideas.iter_mut().find_map(|x| {
+            let pos = x
+                .comments
+                .iter()
+                .position(|x| matches!(x, DbType::DbActionPending(dbid, _) if *dbid == id))?;
+            Some((&mut x.comments, pos))
+        }) else {
+            return Err(());
+        };
+        let old_id = comments[pos].get_entry().id;
+        callback(comments[pos].get_entry_mut());
+        comments[pos].convert_to_db();
+
+        // A refresh might already have loaded the entry from the db
+        let new_id = comments[pos].get_entry().id;
+        if new_id != old_id {
+            self.inserted_comments.insert(new_id);
+        }
+        if let Some(duplicate) = comments
+            .iter()
+            .enumerate()
+            .position(|(i, x)| i != pos && matches!(x, DbType::InDb(x) if x.id == new_id))
+        {
+            comments.remove(duplicate);
+        }
+
+        Ok(())
+    }
+
+    /// Marks everything affected by the action `id` as failed
+    pub fn fail(&mut self, id: usize, error: &str) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        for entry in &mut self.ideas {
+            entry.idea.fail(id, error);
+            for comment in &mut entry.comments {
+                comment.fail(id, error);
+            }
+        }
+        self.reselect(selected);
+    }
+
+    /// Marks everything affected by the failed action `id` as pending again
+    pub fn retry(&mut self, id: usize) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        for entry in &mut self.ideas {
+            entry.idea.retry(id);
+            for comment in &mut entry.comments {
+                comment.retry(id);
+            }
+        }
+        self.reselect(selected);
+    }
+
+    /// Undoes the action `id` on an idea. `previous` is what the idea looked
+    /// like before, or `None` if the action inserted it
+    pub fn rollback(&mut self, id: usize, previous: Option<idea::Model>) {
+        let selected = self.current().map(|x| Key::of(&x.idea));
+        self.deleting.remove(&id);
+        if let Some(pos) = self
+            .ideas
+            .iter()
+            .position(|x| x.idea.action_id() == Some(id))
+        {
+            match previous {
+                Some(previous) => self.ideas[pos].idea = DbType::InDb(previous),
+                None => drop(self.ideas.remove(pos)),
+            }
+        }
+        self.reselect(selected);
+    }
+
+    /// Undoes the action `id` on a comment. `previous` is what the comment
+    /// looked like before, or `None` if the action inserted it
+    pub fn rollback_comment(&mut self, id: usize, previous: Option<comment::Model>) {
+        self.deleting.remove(&id);
+        for entry in &mut self.ideas {
+            match previous {
+                Some(ref previous) => {
+                    for comment in &mut entry.comments {
+                        if comment.action_id() == Some(id) {
+                            *comment = DbType::InDb(previous.clone());
+                        }
+                    }
+                }
+                None => entry.comments.retain(|x| x.action_id() != Some(id)),
+            }
+        }
     }
 
-    pub fn delete<'a>(&self) -> Option<DbActionReturn<'a>> {
-        let selected = self.selected?;
+    /// Removes the comment with the id and the replies to it, like the db
+    /// does when it's deleted
+    fn remove_comment(&mut self, id: i32) {
+        for entry in &mut self.ideas {
+            let mut removed = vec![id];
+            while let Some(id) = removed.pop() {
+                entry.comments.retain(|x| {
+                    let x = x.get_entry();
+                    if x.parent_id == Some(id) {
+                        // Pending replies don't have an id to look for yet
+                        if x.id != -1 {
+                            removed.push(x.id);
+                        }
+                        return false;
+                    }
+                    x.id != id
+                });
+            }
+        }
+    }
 
-        let DbType::InDb(idea::Model { id, .. }) = self.ideas[selected].0 else {
+    /// Deletes the comment with the id, and with it the replies to it
+    pub fn delete_comment<'a>(id: i32) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let comment = view_data
+                .idea
+                .ideas
+                .iter_mut()
+                .flat_map(|x| x.comments.iter_mut())
+                .find(|x| matches!(x, DbType::InDb(x) if x.id == id))?;
+            let action_id = view_data.idea.counter.next();
+            comment.convert_to_db_action(action_id).ok()?;
+            let previous = comment.get_entry().clone();
+            view_data.idea.deleting.insert(action_id);
+
+            Some((
+                action_id,
+                DbActionParts {
+                    description: format!(
+                        "Deleting the comment by {}",
+                        view_data.people.name(previous.author_id)
+                    ),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        async move {
+                            // The replies cascade
+                            eComment::delete_by_id(id).exec(&db).await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(move |view_data: &mut ViewData, _| {
+                        view_data.idea.deleting.remove(&action_id);
+                        view_data.idea.remove_comment(id);
+                    }),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.idea.rollback_comment(action_id, Some(previous));
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Moves the idea with the id to the trash
+    pub fn delete<'a>(id: i32) -> DbActionReturn<'a> {
+        Self::set_deleted_at(id, Some(chrono::Local::now().naive_local()))
+    }
+
+    /// Takes the selected idea back out of the trash
+    pub fn restore<'a>(&self) -> Option<DbActionReturn<'a>> {
+        let DbType::InDb(idea::Model {
+            id,
+            deleted_at: Some(_),
+            ..
+        }) = self.current()?.idea
+        else {
             return None;
         };
+        Some(Self::set_deleted_at(id, None))
+    }
 
-        Some(Box::new(
-            move |view_data: &mut ViewData, conn_opts: ConnectOptions| {
-                let idea = view_data.idea.ideas.iter_mut().find(
-                    |x| matches!(x.0, DbType::InDb(idea::Model {id: model_id, ..}) if id == model_id)
-                )?;
-
-                let Some(counter) = Arc::get_mut(&mut view_data.idea.counter) else {
-                    panic!()
-                };
-                let action_id = counter.next();
-                idea.0.convert_to_db_action(action_id).unwrap();
+    /// Moves the idea with the id in or out of the trash, which moves it
+    /// between the views right away
+    fn set_deleted_at<'a>(id: i32, deleted_at: Option<DateTime>) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let selected = view_data.idea.current().map(|x| Key::of(&x.idea));
+            let (action_id, previous) = view_data
+                .idea
+                .edit_idea(id, |x| x.deleted_at = deleted_at)?;
+            view_data.idea.reselect(selected);
 
-                Some((
-                    action_id,
-                    (
+            let to_update = idea::ActiveModel {
+                id: ActiveValue::Unchanged(id),
+                deleted_at: ActiveValue::Set(deleted_at),
+                ..Default::default()
+            };
+            Some((
+                action_id,
+                DbActionParts {
+                    description: format!(
+                        "{} the idea {:?}",
+                        if deleted_at.is_some() {
+                            "Deleting"
+                        } else {
+                            "Restoring"
+                        },
+                        previous.title
+                    ),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let to_update = to_update.clone();
                         async move {
-                            let db = Database::connect(conn_opts).await?;
+                            eIdea::update(to_update).exec(&db).await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(move |view_data: &mut ViewData, _| {
+                        let _ = view_data.idea.completed(action_id, |_| ());
+                    }),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.idea.rollback(action_id, Some(previous));
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Deletes the idea with the id for good, together with everything
+    /// belonging to it
+    pub fn purge<'a>(id: i32) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let selected = view_data.idea.current().map(|x| Key::of(&x.idea));
+            let idea = view_data.idea.ideas.iter_mut().find(
+                |x| matches!(x.idea, DbType::InDb(idea::Model {id: model_id, ..}) if id == model_id),
+            )?;
+
+            let action_id = view_data.idea.counter.next();
+            idea.idea.convert_to_db_action(action_id).ok()?;
+            let previous = idea.idea.get_entry().clone();
+            view_data.idea.deleting.insert(action_id);
+            view_data.idea.reselect(selected);
 
+            Some((
+                action_id,
+                DbActionParts {
+                    description: format!("Purging the idea {:?}", previous.title),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        async move {
+                            // The comments cascade, but a db that hasn't
+                            // been migrated yet shouldn't end up with
+                            // orphans either
+                            let txn = db.begin().await?;
                             eComment::delete_many()
                                 .filter(comment::Column::CommentsOn.eq(id))
-                                .exec(&db)
+                                .exec(&txn)
                                 .await?;
-                            eIdea::delete_by_id(id).exec(&db).await?;
+                            eIdea::delete_by_id(id).exec(&txn).await?;
+                            txn.commit().await?;
 
                             Ok(None)
                         }
-                        .boxed(),
-                        Box::new(move |view_data: &mut ViewData, new_id: Option<_>| {
-                            assert!(
-                                new_id.is_none(),
-                                "There is probably a bug, this shouldn't be called with Some"
-                            );
-                            let Some(pos) = view_data.idea.ideas.iter_mut().position(
-                                |x| matches!(x.0, DbType::DbActionPending(id, _) if action_id == id)
-                            ) else {
-                                return;
-                            };
-
-                            if view_data.idea.ideas.len() == 1 {
-                                view_data.idea.selected = None;
+                        .boxed()
+                    }),
+                    callback: Box::new(move |view_data: &mut ViewData, new_id: Option<_>| {
+                        assert!(
+                            new_id.is_none(),
+                            "There is probably a bug, this shouldn't be called with Some"
+                        );
+                        view_data.idea.deleting.remove(&action_id);
+                        let Some(pos) = view_data.idea.ideas.iter_mut().position(
+                            |x| matches!(x.idea, DbType::DbActionPending(id, _) if action_id == id),
+                        ) else {
+                            return;
+                        };
+
+                        let selected = view_data.idea.current().map(|x| Key::of(&x.idea));
+                        let _ = view_data.idea.ideas.remove(pos);
+                        view_data.idea.reselect(selected);
+                    }),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.idea.rollback(action_id, Some(previous));
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Deletes the ideas that were moved to the trash before `cutoff` for
+    /// good
+    pub async fn purge_expired(db: &DatabaseConnection, cutoff: DateTime) -> Result<(), DbErr> {
+        eIdea::delete_many()
+            .filter(idea::Column::DeletedAt.lt(cutoff))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Moves the selected idea to `status`, recording that `user` did it
+    pub fn set_status<'a>(&self, status: Status, user: String) -> Option<DbActionReturn<'a>> {
+        let DbType::InDb(idea::Model {
+            id, status: old, ..
+        }) = self.current()?.idea
+        else {
+            return None;
+        };
+        if old == status {
+            return None;
+        }
+
+        Some(Box::new(
+            move |view_data: &mut ViewData, db: DatabaseConnection| {
+                let (action_id, previous) = view_data.idea.edit_idea(id, |x| x.status = status)?;
+                let time = chrono::Local::now().naive_local();
+
+                Some((
+                    action_id,
+                    DbActionParts {
+                        description: format!(
+                            "Marking the idea {:?} as {}",
+                            previous.title,
+                            status.name()
+                        ),
+                        action: Box::new(move || {
+                            let db = db.clone();
+                            let user = user.clone();
+                            async move {
+                                let txn = db.begin().await?;
+                                eIdea::update(idea::ActiveModel {
+                                    id: ActiveValue::Unchanged(id),
+                                    status: ActiveValue::Set(status),
+                                    ..Default::default()
+                                })
+                                .exec(&txn)
+                                .await?;
+                                eStatusHistory::insert(status_history::ActiveModel {
+                                    idea: ActiveValue::Set(id),
+                                    status: ActiveValue::Set(status),
+                                    changed_by: ActiveValue::Set(user),
+                                    time: ActiveValue::Set(time),
+                                    ..Default::default()
+                                })
+                                .exec(&txn)
+                                .await?;
+                                txn.commit().await?;
+
+                                Ok(None)
                             }
+                            .boxed()
+                        }),
+                        callback: Box::new(move |view_data: &mut ViewData, _| {
+                            let _ = view_data.idea.completed(action_id, |_| ());
+                        }),
+                        rollback: Box::new(move |view_data: &mut ViewData| {
+                            view_data.idea.rollback(action_id, Some(previous));
+                        }),
+                    },
+                ))
+            },
+        ))
+    }
 
-                            if view_data.idea.ideas.len() == 1 {
-                                view_data.idea.selected = None;
-                            } else if let Some(ref mut selected) = view_data.idea.selected {
-                                if *selected > pos {
-                                    *selected -= 1;
-                                }
-                                // Just in case so that some weird behavior doesn't crash it
-                                if *selected == view_data.idea.ideas.len() - 1 {
-                                    *selected -= 1;
+    /// Votes for the selected idea as `user`, or takes the vote back if they
+    /// already voted
+    pub fn toggle_vote<'a>(&self, user: String) -> Option<DbActionReturn<'a>> {
+        let entry = self.current()?;
+        let DbType::InDb(idea::Model { id, .. }) = entry.idea else {
+            return None;
+        };
+        let voted = entry.voters.contains(&user);
+
+        Some(Box::new(
+            move |view_data: &mut ViewData, db: DatabaseConnection| {
+                let selected = view_data.idea.current().map(|x| Key::of(&x.idea));
+                let (action_id, previous) = view_data.idea.edit_idea(id, |_| ())?;
+                let entry = view_data
+                    .idea
+                    .ideas
+                    .iter_mut()
+                    .find(|x| x.idea.action_id() == Some(action_id))?;
+                let previous_votes = entry.voters.clone();
+                if voted {
+                    entry.voters.retain(|x| *x != user);
+                } else if let Err(pos) = entry.voters.binary_search(&user) {
+                    entry.voters.insert(pos, user.clone());
+                }
+                // The order may have changed when sorting by votes
+                view_data.idea.reselect(selected);
+                let time = chrono::Local::now().naive_local();
+
+                Some((
+                    action_id,
+                    DbActionParts {
+                        description: format!(
+                            "{} the idea {:?}",
+                            if voted {
+                                "Taking back the vote for"
+                            } else {
+                                "Voting for"
+                            },
+                            previous.title
+                        ),
+                        action: Box::new(move || {
+                            let db = db.clone();
+                            let user = user.clone();
+                            async move {
+                                if voted {
+                                    eVote::delete_by_id((id, user)).exec(&db).await?;
+                                } else {
+                                    eVote::insert(vote::ActiveModel {
+                                        idea: ActiveValue::Set(id),
+                                        voter: ActiveValue::Set(user),
+                                        time: ActiveValue::Set(time),
+                                    })
+                                    .exec_without_returning(&db)
+                                    .await?;
                                 }
+                                Ok(None)
                             }
-                            let _ = view_data.idea.ideas.remove(pos);
+                            .boxed()
                         }),
-                    ),
+                        callback: Box::new(move |view_data: &mut ViewData, _| {
+                            let _ = view_data.idea.completed(action_id, |_| ());
+                        }),
+                        rollback: Box::new(move |view_data: &mut ViewData| {
+                            let selected = view_data.idea.current().map(|x| Key::of(&x.idea));
+                            if let Some(entry) = view_data
+                                .idea
+                                .ideas
+                                .iter_mut()
+                                .find(|x| x.idea.action_id() == Some(action_id))
+                            {
+                                entry.voters = previous_votes;
+                            }
+                            view_data.idea.reselect(selected);
+                            view_data.idea.rollback(action_id, Some(previous));
+                        }),
+                    },
                 ))
             },
         ))
@@ -236,14 +1425,143 @@ impl Idea {
             false
         });
 
-        false
+        // The search has the focus, so the key must not trigger anything else
+        true
+    }
+
+    /// The index into `ideas` of the selected idea
+    fn current_index(&self) -> Option<usize> {
+        self.filtered_indices().nth(self.selected?)
+    }
+
+    pub fn current(&self) -> Option<&IdeaEntry> {
+        Some(&self.ideas[self.current_index()?])
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut IdeaEntry> {
+        let index = self.current_index()?;
+        Some(&mut self.ideas[index])
+    }
+}
+
+/// Replaces the tags on the idea with the id by `tags`, creating the ones
+/// that don't exist yet
+pub(crate) async fn save_tags<C>(db: &C, id: i32, tags: &[String]) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    eIdeaTag::delete_many()
+        .filter(idea_tag::Column::Idea.eq(id))
+        .exec(db)
+        .await?;
+    for name in tags {
+        let tag = match eTag::find()
+            .filter(tag::Column::Name.eq(name))
+            .one(db)
+            .await?
+        {
+            Some(tag) => tag.id,
+            None => {
+                eTag::insert(tag::ActiveModel {
+                    name: ActiveValue::Set(name.clone()),
+                    ..Default::default()
+                })
+                .exec(db)
+                .await?
+                .last_insert_id
+            }
+        };
+        eIdeaTag::insert(idea_tag::ActiveModel {
+            idea: ActiveValue::Set(id),
+            tag: ActiveValue::Set(tag),
+        })
+        .exec_without_returning(db)
+        .await?;
     }
+    Ok(())
+}
 
-    pub fn current(&self) -> Option<&IdeaType> {
-        Some(&self.ideas[self.selected?])
+/// Replaces who is assigned to the idea with the id by `assignees`
+pub(crate) async fn save_assignees<C>(db: &C, id: i32, assignees: &[String]) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    eAssignee::delete_many()
+        .filter(assignee::Column::Idea.eq(id))
+        .exec(db)
+        .await?;
+    for name in assignees {
+        eAssignee::insert(assignee::ActiveModel {
+            idea: ActiveValue::Set(id),
+            name: ActiveValue::Set(name.clone()),
+        })
+        .exec_without_returning(db)
+        .await?;
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, rc::Rc};
+
+    use sea_orm::prelude::DateTime;
+
+    use super::{Idea, Sort};
+    use crate::entities::{
+        idea,
+        sea_orm_active_enums::{Priority, Status},
+    };
+
+    fn ideas() -> Idea {
+        Idea {
+            ideas: Vec::new(),
+            counter: Rc::default(),
+            selected: None,
+            search_query: None,
+            assigned_to: None,
+            trash: false,
+            due_by: None,
+            sort: Sort::default(),
+            comment_cursor: None,
+            collapsed: HashSet::new(),
+            deleting: HashSet::new(),
+            inserted: HashSet::new(),
+            inserted_comments: HashSet::new(),
+        }
+    }
+
+    fn idea(id: i32) -> idea::Model {
+        idea::Model {
+            id,
+            title: "Title".to_string(),
+            description: String::new(),
+            author_id: None,
+            status: Status::New,
+            priority: Priority::Normal,
+            kind: "Issue".to_string(),
+            time: DateTime::default(),
+            edited_at: None,
+            deleted_at: None,
+            anonymous: false,
+            due: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_from_before_an_insert_keeps_it() {
+        let mut ideas = ideas();
+        let action = ideas.new_idea(idea(-1), Vec::new());
+        ideas
+            .completed(action, |x| x.idea.get_entry_mut().id = 7)
+            .unwrap();
+
+        // Fetched while the insert was still in flight
+        ideas.refresh(Vec::new());
+        assert_eq!(ideas.get(7).map(|x| x.id), Some(7));
 
-    pub fn current_mut(&mut self) -> Option<&mut IdeaType> {
-        Some(&mut self.ideas[self.selected?])
+        // Fetched afterwards, so it was deleted by someone else
+        ideas.refresh(Vec::new());
+        assert_eq!(ideas.get(7), None);
     }
 }
@@ -0,0 +1,580 @@
+//! The meetings ideas are discussed in, with their agendas and what was
+//! decided
+use std::{cmp, collections::HashMap};
+
+use futures::FutureExt;
+use sea_orm::{
+    prelude::{DateTime, Expr},
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+
+use crate::{
+    app::{DbActionParts, DbActionReturn},
+    entities::{
+        agenda_item, meeting, meeting_attendee,
+        prelude::{
+            AgendaItem as eAgendaItem, Meeting as eMeeting, MeetingAttendee as eMeetingAttendee,
+        },
+    },
+};
+
+use super::{
+    people::{find_or_create, People},
+    ViewData,
+};
+
+/// A meeting with everything that belongs to it
+#[derive(Debug, Clone)]
+pub struct Meeting {
+    pub meeting: meeting::Model,
+    /// The ids of the people who attend
+    pub attendees: Vec<i32>,
+    /// The ideas that are discussed, in order
+    pub agenda: Vec<agenda_item::Model>,
+}
+
+#[derive(Debug, Default)]
+pub struct Meetings {
+    /// The latest first. Meetings that aren't in the db yet have a negative
+    /// id
+    meetings: Vec<Meeting>,
+    pub selected: Option<usize>,
+    /// The index of the selected item on the agenda of the selected meeting
+    item_cursor: Option<usize>,
+}
+
+impl Meetings {
+    /// The `meetings` as loaded by [`Self::fetch`]
+    pub fn new(meetings: Vec<Meeting>) -> Self {
+        let mut ret = Self::default();
+        ret.refresh(meetings);
+        ret
+    }
+
+    /// Loads every meeting
+    pub async fn fetch(db: &DatabaseConnection) -> Result<Vec<Meeting>, DbErr> {
+        let mut attendees = HashMap::<_, Vec<_>>::new();
+        for attendee in eMeetingAttendee::find().all(db).await? {
+            attendees
+                .entry(attendee.meeting)
+                .or_default()
+                .push(attendee.person);
+        }
+        let mut agendas = HashMap::<_, Vec<_>>::new();
+        for item in eAgendaItem::find()
+            .order_by_asc(agenda_item::Column::Position)
+            .all(db)
+            .await?
+        {
+            agendas.entry(item.meeting).or_default().push(item);
+        }
+        Ok(eMeeting::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|meeting| Meeting {
+                attendees: attendees.remove(&meeting.id).unwrap_or_default(),
+                agenda: agendas.remove(&meeting.id).unwrap_or_default(),
+                meeting,
+            })
+            .collect())
+    }
+
+    /// Loads the meeting with the id, if it still exists
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    pub async fn fetch_one<C>(db: &C, id: i32) -> Result<Option<Meeting>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let Some(meeting) = eMeeting::find_by_id(id).one(db).await? else {
+            return Ok(None);
+        };
+        Ok(Some(Meeting {
+            attendees: eMeetingAttendee::find()
+                .filter(meeting_attendee::Column::Meeting.eq(id))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|x| x.person)
+                .collect(),
+            agenda: eAgendaItem::find()
+                .filter(agenda_item::Column::Meeting.eq(id))
+                .order_by_asc(agenda_item::Column::Position)
+                .all(db)
+                .await?,
+            meeting,
+        }))
+    }
+
+    /// Replaces every meeting by the fetched ones. The ones that are still
+    /// being added are kept
+    pub fn refresh(&mut self, fetched: Vec<Meeting>) {
+        let selected = self.current().map(|x| x.meeting.id);
+        self.meetings.retain(|x| x.meeting.id < 0);
+        self.meetings.extend(fetched);
+        self.sort();
+        self.reselect(selected);
+    }
+
+    /// Applies a meeting someone else added or changed
+    pub fn update(&mut self, meeting: Meeting) {
+        let selected = self.current().map(|x| x.meeting.id);
+        match self.get_mut(meeting.meeting.id) {
+            Some(x) => *x = meeting,
+            None => self.meetings.push(meeting),
+        }
+        self.sort();
+        self.reselect(selected);
+    }
+
+    /// Forgets the meeting with the id
+    pub fn remove(&mut self, id: i32) {
+        let selected = self.current().map(|x| x.meeting.id);
+        self.meetings.retain(|x| x.meeting.id != id);
+        self.reselect(selected);
+    }
+
+    fn sort(&mut self) {
+        self.meetings.sort_by_key(|x| cmp::Reverse(x.meeting.time));
+    }
+
+    pub fn get(&self, id: i32) -> Option<&Meeting> {
+        self.meetings.iter().find(|x| x.meeting.id == id)
+    }
+
+    fn get_mut(&mut self, id: i32) -> Option<&mut Meeting> {
+        self.meetings.iter_mut().find(|x| x.meeting.id == id)
+    }
+
+    /// Every meeting, the latest first
+    pub fn all(&self) -> &[Meeting] {
+        &self.meetings
+    }
+
+    /// Moves the meetings the person with the id `from` attends to `into`,
+    /// the same way [`merge`] does in the db
+    pub fn merge(&mut self, from: i32, into: i32) {
+        for meeting in &mut self.meetings {
+            if !meeting.attendees.contains(&from) {
+                continue;
+            }
+            meeting.attendees.retain(|&x| x != from);
+            if !meeting.attendees.contains(&into) {
+                meeting.attendees.push(into);
+            }
+        }
+    }
+
+    /// Selects the meeting with the id again, keeping the index within
+    /// bounds if it's gone
+    fn reselect(&mut self, id: Option<i32>) {
+        if self.meetings.is_empty() {
+            self.selected = None;
+        } else if let Some(pos) =
+            id.and_then(|id| self.meetings.iter().position(|x| x.meeting.id == id))
+        {
+            self.selected = Some(pos);
+        } else if let Some(selected) = self.selected {
+            self.selected = Some(selected.min(self.meetings.len() - 1));
+        }
+        let items = self.current().map_or(0, |x| x.agenda.len());
+        self.item_cursor = self
+            .item_cursor
+            .filter(|_| items > 0)
+            .map(|x| x.min(items - 1));
+    }
+
+    pub fn current(&self) -> Option<&Meeting> {
+        self.meetings.get(self.selected?)
+    }
+
+    pub fn item_cursor(&self) -> Option<usize> {
+        self.item_cursor
+    }
+
+    /// The selected item on the agenda
+    pub fn current_item(&self) -> Option<&agenda_item::Model> {
+        self.current()?.agenda.get(self.item_cursor?)
+    }
+
+    pub fn down(&mut self) {
+        let count = self.meetings.len();
+        self.item_cursor = None;
+        self.selected = (count > 0).then(|| self.selected.map_or(0, |x| (x + 1) % count));
+    }
+
+    pub fn up(&mut self) {
+        let count = self.meetings.len();
+        self.item_cursor = None;
+        self.selected =
+            (count > 0).then(|| self.selected.map_or(count - 1, |x| (x + count - 1) % count));
+    }
+
+    /// Selects the next item on the agenda
+    pub fn next_item(&mut self) {
+        let count = self.current().map_or(0, |x| x.agenda.len());
+        self.item_cursor = (count > 0).then(|| self.item_cursor.map_or(0, |x| (x + 1) % count));
+    }
+
+    /// Selects the previous item on the agenda
+    pub fn prev_item(&mut self) {
+        let count = self.current().map_or(0, |x| x.agenda.len());
+        self.item_cursor = (count > 0).then(|| {
+            self.item_cursor
+                .map_or(count - 1, |x| (x + count - 1) % count)
+        });
+    }
+
+    /// Adds a meeting, or changes the one with the id
+    pub fn save<'a>(
+        id: Option<i32>,
+        title: String,
+        time: DateTime,
+        attendees: Vec<String>,
+    ) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let meetings = &mut view_data.meetings;
+            let selected = meetings.current().map(|x| x.meeting.id);
+            let previous = match id {
+                // Pending meetings can't be changed until they have an id
+                Some(id) if id < 0 => return None,
+                Some(id) => Some(meetings.get(id)?.clone()),
+                None => None,
+            };
+            let local_id = id.unwrap_or_else(|| {
+                meetings
+                    .meetings
+                    .iter()
+                    .map(|x| x.meeting.id)
+                    .min()
+                    .unwrap_or(0)
+                    .min(0)
+                    - 1
+            });
+            let model = meeting::Model {
+                id: local_id,
+                title: title.clone(),
+                time,
+            };
+            let mut ids = Vec::new();
+            for name in &attendees {
+                let id = view_data.people.id_or_insert(name);
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+            let meetings = &mut view_data.meetings;
+            meetings.update(Meeting {
+                meeting: model.clone(),
+                attendees: ids,
+                agenda: previous
+                    .as_ref()
+                    .map_or_else(Vec::new, |x| x.agenda.clone()),
+            });
+            // A new meeting is selected, so its agenda can be made right away
+            meetings.reselect(if id.is_some() {
+                selected
+            } else {
+                Some(local_id)
+            });
+
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: if id.is_some() {
+                        format!("Changing the meeting {title:?}")
+                    } else {
+                        format!("Adding the meeting {title:?}")
+                    },
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let model = model.clone();
+                        let attendees = attendees.clone();
+                        async move {
+                            let txn = db.begin().await?;
+                            let active_model = meeting::ActiveModel {
+                                title: ActiveValue::Set(model.title),
+                                time: ActiveValue::Set(model.time),
+                                ..Default::default()
+                            };
+                            let id = if let Some(id) = id {
+                                eMeeting::update(meeting::ActiveModel {
+                                    id: ActiveValue::Unchanged(id),
+                                    ..active_model
+                                })
+                                .exec(&txn)
+                                .await?;
+                                id
+                            } else {
+                                eMeeting::insert(active_model)
+                                    .exec(&txn)
+                                    .await?
+                                    .last_insert_id
+                            };
+                            eMeetingAttendee::delete_many()
+                                .filter(meeting_attendee::Column::Meeting.eq(id))
+                                .exec(&txn)
+                                .await?;
+                            let mut inserted = Vec::new();
+                            for name in attendees {
+                                let person = find_or_create(&txn, &name).await?;
+                                // Two spellings of the same person
+                                if inserted.contains(&person) {
+                                    continue;
+                                }
+                                eMeetingAttendee::insert(meeting_attendee::ActiveModel {
+                                    meeting: ActiveValue::Set(id),
+                                    person: ActiveValue::Set(person),
+                                })
+                                .exec(&txn)
+                                .await?;
+                                inserted.push(person);
+                            }
+                            txn.commit().await?;
+                            Ok(Some(id))
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(move |view_data: &mut ViewData, new_id: Option<i32>| {
+                        if let (Some(meeting), Some(new_id)) =
+                            (view_data.meetings.get_mut(local_id), new_id)
+                        {
+                            meeting.meeting.id = new_id;
+                            for item in &mut meeting.agenda {
+                                item.meeting = new_id;
+                            }
+                        }
+                    }),
+                    rollback: Box::new(move |view_data: &mut ViewData| match previous {
+                        Some(previous) => view_data.meetings.update(previous),
+                        None => view_data.meetings.remove(local_id),
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Deletes the meeting with the id, with its agenda
+    pub fn delete<'a>(id: i32) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            if id < 0 {
+                return None;
+            }
+            let previous = view_data.meetings.get(id)?.clone();
+            view_data.meetings.remove(id);
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: format!("Deleting the meeting {:?}", previous.meeting.title),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        async move {
+                            eMeeting::delete_by_id(id).exec(&db).await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(|_, _| ()),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.meetings.update(previous);
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Puts the `ideas` on the agenda of the meeting with the id, in that
+    /// order. What was decided about the ideas that stay on it is kept
+    pub fn set_agenda<'a>(id: i32, ideas: Vec<i32>) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            if id < 0 {
+                return None;
+            }
+            let meeting = view_data.meetings.get_mut(id)?;
+            let agenda = ideas
+                .iter()
+                .zip(0..)
+                .map(|(&idea, position)| agenda_item::Model {
+                    meeting: id,
+                    idea,
+                    position,
+                    decision: meeting
+                        .agenda
+                        .iter()
+                        .find(|x| x.idea == idea)
+                        .map_or_else(String::new, |x| x.decision.clone()),
+                })
+                .collect::<Vec<_>>();
+            let previous = std::mem::replace(&mut meeting.agenda, agenda.clone());
+            let title = meeting.meeting.title.clone();
+            view_data.meetings.reselect(Some(id));
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: format!("Changing the agenda of {title:?}"),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let agenda = agenda.clone();
+                        async move {
+                            let txn = db.begin().await?;
+                            eAgendaItem::delete_many()
+                                .filter(agenda_item::Column::Meeting.eq(id))
+                                .exec(&txn)
+                                .await?;
+                            for item in agenda {
+                                eAgendaItem::insert(agenda_item::ActiveModel {
+                                    meeting: ActiveValue::Set(item.meeting),
+                                    idea: ActiveValue::Set(item.idea),
+                                    position: ActiveValue::Set(item.position),
+                                    decision: ActiveValue::Set(item.decision),
+                                })
+                                .exec(&txn)
+                                .await?;
+                            }
+                            txn.commit().await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(|_, _| ()),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        if let Some(meeting) = view_data.meetings.get_mut(id) {
+                            meeting.agenda = previous;
+                        }
+                        view_data.meetings.reselect(Some(id));
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Records what was decided about the idea with the id `idea` in the
+    /// meeting with the id `id`
+    pub fn set_decision<'a>(id: i32, idea: i32, decision: String) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let item = view_data
+                .meetings
+                .get_mut(id)?
+                .agenda
+                .iter_mut()
+                .find(|x| x.idea == idea)?;
+            let previous = std::mem::replace(&mut item.decision, decision.clone());
+            let title = view_data
+                .idea
+                .get(idea)
+                .map_or_else(String::new, |x| x.title.clone());
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: format!("Recording the decision on {title:?}"),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let decision = decision.clone();
+                        async move {
+                            eAgendaItem::update(agenda_item::ActiveModel {
+                                meeting: ActiveValue::Unchanged(id),
+                                idea: ActiveValue::Unchanged(idea),
+                                decision: ActiveValue::Set(decision),
+                                ..Default::default()
+                            })
+                            .exec(&db)
+                            .await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(|_, _| ()),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        if let Some(item) = view_data
+                            .meetings
+                            .get_mut(id)
+                            .and_then(|x| x.agenda.iter_mut().find(|x| x.idea == idea))
+                        {
+                            item.decision = previous;
+                        }
+                    }),
+                },
+            ))
+        })
+    }
+}
+
+impl Meeting {
+    /// The names of the people who attend, sorted
+    pub fn attendee_names(&self, people: &People) -> Vec<String> {
+        let mut names = self
+            .attendees
+            .iter()
+            .map(|&x| people.name(x).to_string())
+            .collect::<Vec<_>>();
+        names.sort_by_cached_key(|x| x.to_lowercase());
+        names
+    }
+
+    /// The minutes of the meeting as Markdown, with the ideas and people
+    /// taken from `view_data`
+    pub fn minutes(&self, view_data: &ViewData) -> String {
+        let mut minutes = format!(
+            "# {}\n{}\n",
+            self.meeting.title,
+            self.meeting.time.format("%d/%m/%Y %H:%M")
+        );
+        if !self.attendees.is_empty() {
+            minutes += &format!(
+                "\n**Attendees:** {}\n",
+                self.attendee_names(&view_data.people).join(", ")
+            );
+        }
+        // Purged ideas are gone from the agenda as well
+        let items = self
+            .agenda
+            .iter()
+            .filter_map(|item| Some((item, view_data.idea.get_any(item.idea)?)));
+        for (i, (item, idea)) in items.enumerate() {
+            minutes += &format!(
+                "\n## {}. {}\n*{}, submitted by {} on {}*\n",
+                i + 1,
+                idea.title,
+                idea.status.name(),
+                view_data.author(idea),
+                idea.time.format("%d/%m/%Y")
+            );
+            if !idea.description.is_empty() {
+                minutes += &format!("\n{}\n", idea.description.trim_end());
+            }
+            minutes += &if item.decision.is_empty() {
+                "\n**Decision:** none\n".to_string()
+            } else {
+                format!("\n**Decision:** {}\n", item.decision.trim_end())
+            };
+        }
+        minutes
+    }
+}
+
+/// Moves the meetings the person with the id `from` attends to `into`. The
+/// ones both of them attend are only attended by `into` afterwards
+pub async fn merge<C>(db: &C, from: i32, into: i32) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let attended = eMeetingAttendee::find()
+        .filter(meeting_attendee::Column::Person.eq(into))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|x| x.meeting)
+        .collect::<Vec<_>>();
+    eMeetingAttendee::delete_many()
+        .filter(meeting_attendee::Column::Person.eq(from))
+        .filter(meeting_attendee::Column::Meeting.is_in(attended))
+        .exec(db)
+        .await?;
+    eMeetingAttendee::update_many()
+        .col_expr(meeting_attendee::Column::Person, Expr::value(into))
+        .filter(meeting_attendee::Column::Person.eq(from))
+        .exec(db)
+        .await?;
+    Ok(())
+}
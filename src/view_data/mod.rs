@@ -1,13 +1,27 @@
-use sea_orm::{ConnectOptions, DbErr};
-use std::sync::Arc;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, QueryOrder, Statement,
+};
+use std::rc::Rc;
 
 mod counter;
 pub mod db_type;
 pub mod idea;
+pub mod meeting;
+pub mod people;
+pub mod roster;
 pub mod search_query;
 
 use counter::Counter;
 use idea::Idea;
+use meeting::Meetings;
+use people::People;
+use roster::Roster;
+
+use crate::entities::{
+    self, kind, person,
+    prelude::{Kind as eKind, Moderator as eModerator, Tag as eTag},
+    tag,
+};
 // use crate::entities::{idea, prelude::Idea as eIdea};
 
 /// `ViewData` is used to store information that is neccessary for the
@@ -15,18 +29,141 @@ use idea::Idea;
 #[derive(Debug)]
 pub struct ViewData {
     pub idea: Idea,
+    /// The kinds an idea can be filed as, in the order they are offered
+    pub kinds: Vec<String>,
+    /// The names of all tags that exist, sorted. Used for completing tags
+    pub tags: Vec<String>,
+    pub people: People,
+    pub roster: Roster,
+    pub meetings: Meetings,
+    /// Hands out the ids of actions that don't belong to an idea
+    counter: Rc<Counter>,
 }
 
 impl ViewData {
-    pub async fn new(conn_opts: &ConnectOptions) -> Result<Self, DbErr> {
-        let counter = Arc::new(Counter::default());
+    pub async fn new(db: &DatabaseConnection) -> Result<Self, DbErr> {
+        let counter = Rc::new(Counter::default());
+        let people = People::new(People::fetch(db).await?);
         Ok(Self {
-            idea: Idea::new(conn_opts, Arc::clone(&counter)).await?,
+            idea: Idea::new(db, Rc::clone(&counter)).await?,
+            kinds: fetch_kinds(db).await?,
+            tags: fetch_tags(db).await?,
+            roster: Roster::new(Roster::fetch(db).await?, &people),
+            meetings: Meetings::new(Meetings::fetch(db).await?),
+            people,
+            counter,
         })
     }
 
-    pub async fn refresh(&mut self, conn_opts: &ConnectOptions) -> Result<(), DbErr> {
-        self.idea.refresh(conn_opts).await;
-        Ok(())
+    /// Loads everything needed by [`Self::refresh`]. This doesn't touch
+    /// `self`, so it can run in the background
+    pub async fn fetch(db: &DatabaseConnection) -> Result<Snapshot, DbErr> {
+        Ok(Snapshot {
+            ideas: Idea::fetch(db).await?,
+            kinds: fetch_kinds(db).await?,
+            tags: fetch_tags(db).await?,
+            people: People::fetch(db).await?,
+            roster: Roster::fetch(db).await?,
+            meetings: Meetings::fetch(db).await?,
+        })
+    }
+
+    /// Merges the fetched `snapshot` into the local state
+    pub fn refresh(&mut self, snapshot: Snapshot) {
+        self.idea.refresh(snapshot.ideas);
+        self.kinds = snapshot.kinds;
+        self.tags = snapshot.tags;
+        self.people.refresh(snapshot.people);
+        self.roster.refresh(snapshot.roster, &self.people);
+        self.meetings.refresh(snapshot.meetings);
+    }
+
+    /// Applies a change someone else made to the db
+    pub fn apply(&mut self, change: idea::Change) {
+        match change {
+            idea::Change::Person(person) => {
+                self.people.update(person);
+                self.roster.sort(&self.people);
+            }
+            idea::Change::PersonDeleted(id) => self.people.remove(id),
+            idea::Change::Roster(roster) => self.roster.update(roster, &self.people),
+            idea::Change::RosterDeleted(id) => self.roster.remove(id),
+            idea::Change::RosterIdeas(id, ideas) => self.roster.set_ideas(id, ideas),
+            idea::Change::Meeting(meeting) => self.meetings.update(meeting),
+            idea::Change::MeetingDeleted(id) => self.meetings.remove(id),
+            change => {
+                if let idea::Change::Tags(_, ref tags) = change {
+                    self.add_tags(tags);
+                }
+                self.idea.apply(change);
+            }
+        }
+    }
+
+    /// The name of whoever submitted the idea, or
+    /// [`ANONYMOUS`](entities::idea::ANONYMOUS)
+    pub fn author(&self, idea: &entities::idea::Model) -> &str {
+        idea.author_id
+            .map_or(entities::idea::ANONYMOUS, |id| self.people.name(id))
+    }
+
+    /// Makes the tags available for completion, as they exist now
+    pub fn add_tags(&mut self, tags: &[String]) {
+        for tag in tags {
+            if let Err(pos) = self.tags.binary_search(tag) {
+                self.tags.insert(pos, tag.clone());
+            }
+        }
+    }
+}
+
+/// The state of the db at some point, as loaded by [`ViewData::fetch`]
+#[derive(Debug)]
+pub struct Snapshot {
+    ideas: idea::Snapshot,
+    kinds: Vec<String>,
+    tags: Vec<String>,
+    people: Vec<person::Model>,
+    roster: roster::Snapshot,
+    meetings: Vec<meeting::Meeting>,
+}
+
+async fn fetch_kinds(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    Ok(eKind::find()
+        .order_by_asc(kind::Column::Position)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|x| x.name)
+        .collect())
+}
+
+async fn fetch_tags(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    Ok(eTag::find()
+        .order_by_asc(tag::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|x| x.name)
+        .collect())
+}
+
+/// Whether `user` is a moderator, and so may see who submitted anonymous
+/// ideas. On postgres that's up to the database, which only lets members of
+/// the `verneanbud_moderator` role read them, whoever `user` claims to be.
+/// The moderator table only decides where that role couldn't be created
+pub async fn is_moderator(db: &DatabaseConnection, user: &str) -> Result<bool, DbErr> {
+    if db.get_database_backend() == DbBackend::Postgres {
+        let row = db
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT pg_has_role(current_user, oid, 'MEMBER') AS moderator \
+                 FROM pg_roles WHERE rolname = 'verneanbud_moderator'",
+            ))
+            .await?;
+        if let Some(row) = row {
+            return row.try_get("", "moderator");
+        }
     }
+    Ok(eModerator::find_by_id(user).one(db).await?.is_some())
 }
@@ -0,0 +1,221 @@
+//! Everyone who wrote an idea or a comment
+use futures::FutureExt;
+use sea_orm::{
+    sea_query::Expr, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, TransactionTrait,
+};
+
+use crate::{
+    app::{DbActionParts, DbActionReturn},
+    entities::{
+        comment, idea, person,
+        prelude::{Comment as eComment, Idea as eIdea, Person as ePerson},
+    },
+};
+
+use super::{meeting, roster, ViewData};
+
+#[derive(Debug)]
+pub struct People {
+    /// Sorted by name. People that aren't in the db yet have a negative id
+    people: Vec<person::Model>,
+}
+
+impl People {
+    /// Everyone in `people`, which is sorted by name
+    pub const fn new(people: Vec<person::Model>) -> Self {
+        Self { people }
+    }
+
+    /// Loads everyone, sorted by name
+    pub async fn fetch(db: &DatabaseConnection) -> Result<Vec<person::Model>, DbErr> {
+        let mut people = ePerson::find().all(db).await?;
+        people.sort_by_cached_key(|x| x.name.to_lowercase());
+        Ok(people)
+    }
+
+    /// Replaces everyone by the `fetched` people. The ones that are still
+    /// being added are kept
+    pub fn refresh(&mut self, fetched: Vec<person::Model>) {
+        let pending = self
+            .people
+            .drain(..)
+            .filter(|x| x.id < 0 && !fetched.iter().any(|y| same_name(&x.name, &y.name)))
+            .collect::<Vec<_>>();
+        self.people = fetched;
+        for person in pending {
+            self.insert(person);
+        }
+    }
+
+    /// Applies a person someone else added or renamed
+    pub fn update(&mut self, person: person::Model) {
+        self.people
+            .retain(|x| x.id != person.id && !(x.id < 0 && same_name(&x.name, &person.name)));
+        self.insert(person);
+    }
+
+    /// Forgets the person with the id
+    pub fn remove(&mut self, id: i32) {
+        self.people.retain(|x| x.id != id);
+    }
+
+    /// The name of the person with the id
+    pub fn name(&self, id: i32) -> &str {
+        self.people
+            .iter()
+            .find(|x| x.id == id)
+            .map_or("?", |x| x.name.as_str())
+    }
+
+    /// The person with the id, if they are in the db
+    pub fn get(&self, id: i32) -> Option<&person::Model> {
+        self.people.iter().find(|x| x.id == id && id >= 0)
+    }
+
+    /// Everyone's name, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.people.iter().map(|x| x.name.clone()).collect()
+    }
+
+    /// The person called `name`, ignoring case and whitespace
+    pub fn find(&self, name: &str) -> Option<&person::Model> {
+        self.people.iter().find(|x| same_name(&x.name, name))
+    }
+
+    /// The id of the person called `name`. Someone who doesn't exist yet is
+    /// added with a negative id, until [`find_or_create`] has added them to
+    /// the db and the people are refreshed
+    pub fn id_or_insert(&mut self, name: &str) -> i32 {
+        if let Some(person) = self.find(name) {
+            return person.id;
+        }
+        let id = self.people.iter().map(|x| x.id).min().unwrap_or(0).min(0) - 1;
+        self.insert(person::Model {
+            id,
+            name: normalize(name),
+        });
+        id
+    }
+
+    fn insert(&mut self, person: person::Model) {
+        let pos = self
+            .people
+            .partition_point(|x| x.name.to_lowercase() < person.name.to_lowercase());
+        self.people.insert(pos, person);
+    }
+
+    /// Merges the person with the id `from` into the one with the id `into`.
+    /// Everything `from` wrote or attended is then by `into`, and `from` is
+    /// gone
+    pub fn merge<'a>(from: i32, into: i32) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            if from == into || from < 0 || into < 0 {
+                return None;
+            }
+            let description = format!(
+                "Merging {} into {}",
+                view_data.people.name(from),
+                view_data.people.name(into)
+            );
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description,
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        async move {
+                            let txn = db.begin().await?;
+                            eIdea::update_many()
+                                .col_expr(idea::Column::AuthorId, Expr::value(into))
+                                .filter(idea::Column::AuthorId.eq(from))
+                                .exec(&txn)
+                                .await?;
+                            eComment::update_many()
+                                .col_expr(comment::Column::AuthorId, Expr::value(into))
+                                .filter(comment::Column::AuthorId.eq(from))
+                                .exec(&txn)
+                                .await?;
+                            roster::merge(&txn, from, into).await?;
+                            meeting::merge(&txn, from, into).await?;
+                            ePerson::delete_by_id(from).exec(&txn).await?;
+                            txn.commit().await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    // Nothing is shown as merged until it is, so there is
+                    // nothing to roll back either
+                    callback: Box::new(move |view_data: &mut ViewData, _| {
+                        view_data.idea.replace_author(from, into);
+                        view_data.roster.merge(from, into, &view_data.people);
+                        view_data.meetings.merge(from, into);
+                        view_data.people.remove(from);
+                    }),
+                    rollback: Box::new(|_| ()),
+                },
+            ))
+        })
+    }
+}
+
+/// The id of the person called `name`, ignoring case and whitespace. They
+/// are added if they don't exist yet
+pub async fn find_or_create<C>(db: &C, name: &str) -> Result<i32, DbErr>
+where
+    C: ConnectionTrait,
+{
+    // Compared here rather than with the db's `lower`, which only folds ASCII
+    // on sqlite, so that "Åse" is found the same way `People` finds her
+    let people = People::new(ePerson::find().all(db).await?);
+    if let Some(person) = people.find(name) {
+        return Ok(person.id);
+    }
+    let name = normalize(name);
+    Ok(ePerson::insert(person::ActiveModel {
+        name: ActiveValue::Set(name),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?
+    .last_insert_id)
+}
+
+/// `name` with its whitespace collapsed, the way names are stored
+pub fn normalize(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether the names belong to the same person
+fn same_name(a: &str, b: &str) -> bool {
+    normalize(a).to_lowercase() == normalize(b).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::People;
+    use crate::entities::person;
+
+    fn people() -> People {
+        People::new(vec![person::Model {
+            id: 1,
+            name: "Åse".to_string(),
+        }])
+    }
+
+    #[test]
+    fn finds_names_in_any_case() {
+        let people = people();
+        assert_eq!(people.find("åse").map(|x| x.id), Some(1));
+        assert_eq!(people.find(" ÅSE ").map(|x| x.id), Some(1));
+        assert!(people.find("Ase").is_none());
+    }
+
+    #[test]
+    fn inserts_only_new_names() {
+        let mut people = people();
+        assert_eq!(people.id_or_insert("åse"), 1);
+        assert_eq!(people.id_or_insert("Ola"), -1);
+        assert_eq!(people.names(), ["Ola", "Åse"]);
+    }
+}
@@ -0,0 +1,463 @@
+//! The people that should be talked to, and the ideas that came out of
+//! talking to them
+use std::collections::HashMap;
+
+use futures::FutureExt;
+use sea_orm::{
+    sqlx::types::chrono, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, TransactionTrait,
+};
+
+use crate::{
+    app::{DbActionParts, DbActionReturn},
+    entities::{
+        prelude::{Roster as eRoster, RosterIdea as eRosterIdea},
+        roster, roster_idea,
+    },
+};
+
+use super::{people::People, ViewData};
+
+/// Everyone on the roster with the ids of the ideas that came out of talking
+/// to them, as loaded by [`Roster::fetch`]
+pub type Snapshot = Vec<(roster::Model, Vec<i32>)>;
+
+/// Someone on the roster
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub roster: roster::Model,
+    /// The ideas that came out of talking to them
+    pub ideas: Vec<i32>,
+}
+
+#[derive(Debug)]
+pub struct Roster {
+    /// Sorted by class, then by name
+    entries: Vec<Entry>,
+    /// The index of the selected entry among the shown ones
+    pub selected: Option<usize>,
+    /// Whether the people that were talked to already are shown too
+    pub show_all: bool,
+}
+
+impl Roster {
+    /// The roster in `snapshot`, sorted by [`Self::sort`]
+    pub fn new(snapshot: Snapshot, people: &People) -> Self {
+        let mut ret = Self {
+            entries: Vec::new(),
+            selected: None,
+            show_all: false,
+        };
+        ret.refresh(snapshot, people);
+        ret
+    }
+
+    /// Loads everyone on the roster
+    pub async fn fetch(db: &DatabaseConnection) -> Result<Snapshot, DbErr> {
+        let mut ideas = HashMap::<_, Vec<_>>::new();
+        for link in eRosterIdea::find().all(db).await? {
+            ideas.entry(link.person).or_default().push(link.idea);
+        }
+        Ok(eRoster::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|x| {
+                let ideas = ideas.remove(&x.person).unwrap_or_default();
+                (x, ideas)
+            })
+            .collect())
+    }
+
+    /// Replaces everyone by the fetched ones
+    pub fn refresh(&mut self, snapshot: Snapshot, people: &People) {
+        let selected = self.current().map(|x| x.roster.person);
+        self.entries = snapshot
+            .into_iter()
+            .map(|(roster, ideas)| Entry { roster, ideas })
+            .collect();
+        self.sort(people);
+        self.reselect(selected);
+    }
+
+    /// Sorts the entries by class, then by name. Needed whenever someone is
+    /// renamed
+    pub fn sort(&mut self, people: &People) {
+        let selected = self.current().map(|x| x.roster.person);
+        self.entries.sort_by_cached_key(|x| {
+            (
+                x.roster.class.to_lowercase(),
+                people.name(x.roster.person).to_lowercase(),
+            )
+        });
+        self.reselect(selected);
+    }
+
+    /// Applies an entry someone else added or changed
+    pub fn update(&mut self, roster: roster::Model, people: &People) {
+        match self.get_mut(roster.person) {
+            Some(entry) => entry.roster = roster,
+            None => self.entries.push(Entry {
+                roster,
+                ideas: Vec::new(),
+            }),
+        }
+        self.sort(people);
+    }
+
+    /// Takes the person with the id off the roster
+    pub fn remove(&mut self, person: i32) {
+        let selected = self.current().map(|x| x.roster.person);
+        self.entries.retain(|x| x.roster.person != person);
+        self.reselect(selected);
+    }
+
+    /// Sets the ideas that came out of talking to the person with the id
+    pub fn set_ideas(&mut self, person: i32, ideas: Vec<i32>) {
+        if let Some(entry) = self.get_mut(person) {
+            entry.ideas = ideas;
+        }
+    }
+
+    /// Replaces the entry with the same person by `roster`
+    fn replace(&mut self, roster: roster::Model) {
+        let selected = self.current().map(|x| x.roster.person);
+        if let Some(entry) = self.get_mut(roster.person) {
+            entry.roster = roster;
+        }
+        self.reselect(selected);
+    }
+
+    /// Moves everything about the person with the id `from` to `into`, the
+    /// same way [`merge`] does in the db
+    pub fn merge(&mut self, from: i32, into: i32, people: &People) {
+        let Some(pos) = self.entries.iter().position(|x| x.roster.person == from) else {
+            return;
+        };
+        let from = self.entries.remove(pos);
+        match self.get_mut(into) {
+            Some(entry) => {
+                entry.roster = merged(Some(entry.roster.clone()), from.roster, into);
+                for idea in from.ideas {
+                    if !entry.ideas.contains(&idea) {
+                        entry.ideas.push(idea);
+                    }
+                }
+            }
+            None => self.entries.push(Entry {
+                roster: merged(None, from.roster, into),
+                ideas: from.ideas,
+            }),
+        }
+        self.sort(people);
+    }
+
+    pub fn get(&self, person: i32) -> Option<&Entry> {
+        self.entries.iter().find(|x| x.roster.person == person)
+    }
+
+    fn get_mut(&mut self, person: i32) -> Option<&mut Entry> {
+        self.entries.iter_mut().find(|x| x.roster.person == person)
+    }
+
+    /// The entries that are listed. Unless [`Self::show_all`] is set, that's
+    /// only the people nobody has talked to yet
+    pub fn shown(&self) -> impl Iterator<Item = &Entry> + Clone {
+        let show_all = self.show_all;
+        self.entries
+            .iter()
+            .filter(move |x| show_all || x.roster.contacted_at.is_none())
+    }
+
+    /// How many people are on the roster, and how many of them are left to
+    /// talk to
+    pub fn counts(&self) -> (usize, usize) {
+        (
+            self.entries.len(),
+            self.entries
+                .iter()
+                .filter(|x| x.roster.contacted_at.is_none())
+                .count(),
+        )
+    }
+
+    /// The people whose conversations the idea with the id came out of
+    pub fn people_with_idea(&self, idea: i32) -> impl Iterator<Item = i32> + '_ {
+        self.entries
+            .iter()
+            .filter(move |x| x.ideas.contains(&idea))
+            .map(|x| x.roster.person)
+    }
+
+    pub fn current(&self) -> Option<&Entry> {
+        self.shown().nth(self.selected?)
+    }
+
+    pub fn down(&mut self) {
+        let count = self.shown().count();
+        self.selected = (count > 0).then(|| self.selected.map_or(0, |x| (x + 1) % count));
+    }
+
+    pub fn up(&mut self) {
+        let count = self.shown().count();
+        self.selected =
+            (count > 0).then(|| self.selected.map_or(count - 1, |x| (x + count - 1) % count));
+    }
+
+    /// Switches between showing everyone and only the people left to talk to
+    pub fn toggle_show_all(&mut self) {
+        let selected = self.current().map(|x| x.roster.person);
+        self.show_all = !self.show_all;
+        self.reselect(selected);
+    }
+
+    /// Selects the person with the id again. If they aren't shown anymore,
+    /// whoever took their place is selected instead, so working through the
+    /// list doesn't jump back to the top
+    fn reselect(&mut self, person: Option<i32>) {
+        let count = self.shown().count();
+        if count == 0 {
+            self.selected = None;
+            return;
+        }
+        if let Some(pos) = person.and_then(|id| self.shown().position(|x| x.roster.person == id)) {
+            self.selected = Some(pos);
+        } else if let Some(selected) = self.selected {
+            self.selected = Some(selected.min(count - 1));
+        }
+    }
+
+    /// Marks the selected person as talked to by `user`, or as not talked to
+    /// yet if they already were
+    pub fn toggle_contacted<'a>(&self, user: String) -> Option<DbActionReturn<'a>> {
+        let person = self.current()?.roster.person;
+        Some(Box::new(
+            move |view_data: &mut ViewData, db: DatabaseConnection| {
+                let previous = view_data.roster.get(person)?.roster.clone();
+                let contacted = previous.contacted_at.is_none();
+                let updated = roster::Model {
+                    contacted_at: contacted.then(|| chrono::Local::now().naive_local()),
+                    contacted_by: contacted.then_some(user),
+                    ..previous.clone()
+                };
+                view_data.roster.replace(updated.clone());
+                Some((
+                    view_data.counter.next(),
+                    DbActionParts {
+                        description: format!(
+                            "Marking {} as {}",
+                            view_data.people.name(person),
+                            if contacted {
+                                "talked to"
+                            } else {
+                                "not talked to yet"
+                            }
+                        ),
+                        action: Box::new(move || {
+                            let db = db.clone();
+                            let updated = updated.clone();
+                            async move {
+                                eRoster::update(roster::ActiveModel {
+                                    person: ActiveValue::Unchanged(updated.person),
+                                    contacted_at: ActiveValue::Set(updated.contacted_at),
+                                    contacted_by: ActiveValue::Set(updated.contacted_by),
+                                    ..Default::default()
+                                })
+                                .exec(&db)
+                                .await?;
+                                Ok(None)
+                            }
+                            .boxed()
+                        }),
+                        callback: Box::new(|_, _| ()),
+                        rollback: Box::new(move |view_data: &mut ViewData| {
+                            view_data.roster.replace(previous);
+                        }),
+                    },
+                ))
+            },
+        ))
+    }
+
+    /// Changes the class of the person with the id, and the notes from
+    /// talking to them
+    pub fn edit<'a>(person: i32, class: String, notes: String) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let previous = view_data.roster.get(person)?.roster.clone();
+            let updated = roster::Model {
+                class,
+                notes,
+                ..previous.clone()
+            };
+            view_data.roster.replace(updated.clone());
+            view_data.roster.sort(&view_data.people);
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: format!("Saving the notes on {}", view_data.people.name(person)),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let updated = updated.clone();
+                        async move {
+                            eRoster::update(roster::ActiveModel {
+                                person: ActiveValue::Unchanged(updated.person),
+                                class: ActiveValue::Set(updated.class),
+                                notes: ActiveValue::Set(updated.notes),
+                                ..Default::default()
+                            })
+                            .exec(&db)
+                            .await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(|_, _| ()),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.roster.replace(previous);
+                        view_data.roster.sort(&view_data.people);
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Replaces the ideas that came out of talking to the person with the id
+    /// by `ideas`
+    pub fn link_ideas<'a>(person: i32, ideas: Vec<i32>) -> DbActionReturn<'a> {
+        Box::new(move |view_data: &mut ViewData, db: DatabaseConnection| {
+            let previous = view_data.roster.get(person)?.ideas.clone();
+            view_data.roster.set_ideas(person, ideas.clone());
+            Some((
+                view_data.counter.next(),
+                DbActionParts {
+                    description: format!(
+                        "Linking the ideas from talking to {}",
+                        view_data.people.name(person)
+                    ),
+                    action: Box::new(move || {
+                        let db = db.clone();
+                        let ideas = ideas.clone();
+                        async move {
+                            let txn = db.begin().await?;
+                            eRosterIdea::delete_many()
+                                .filter(roster_idea::Column::Person.eq(person))
+                                .exec(&txn)
+                                .await?;
+                            for idea in ideas {
+                                eRosterIdea::insert(roster_idea::ActiveModel {
+                                    person: ActiveValue::Set(person),
+                                    idea: ActiveValue::Set(idea),
+                                })
+                                .exec(&txn)
+                                .await?;
+                            }
+                            txn.commit().await?;
+                            Ok(None)
+                        }
+                        .boxed()
+                    }),
+                    callback: Box::new(|_, _| ()),
+                    rollback: Box::new(move |view_data: &mut ViewData| {
+                        view_data.roster.set_ideas(person, previous);
+                    }),
+                },
+            ))
+        })
+    }
+
+    /// Loads the ideas linked to the person with the id
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    pub async fn fetch_ideas(db: &DatabaseConnection, person: i32) -> Result<Vec<i32>, DbErr> {
+        Ok(eRosterIdea::find()
+            .filter(roster_idea::Column::Person.eq(person))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|x| x.idea)
+            .collect())
+    }
+}
+
+/// Moves the person with the id `from` on the roster to `into`, as part of
+/// merging them. If both are on it, their notes are kept together
+pub async fn merge<C>(db: &C, from: i32, into: i32) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let Some(from_entry) = eRoster::find_by_id(from).one(db).await? else {
+        return Ok(());
+    };
+    let into_entry = eRoster::find_by_id(into).one(db).await?;
+    let exists = into_entry.is_some();
+    let entry = merged(into_entry, from_entry, into);
+    let active_model = roster::ActiveModel {
+        person: ActiveValue::Set(entry.person),
+        class: ActiveValue::Set(entry.class),
+        contacted_at: ActiveValue::Set(entry.contacted_at),
+        contacted_by: ActiveValue::Set(entry.contacted_by),
+        notes: ActiveValue::Set(entry.notes),
+    };
+    if exists {
+        eRoster::update(active_model).exec(db).await?;
+    } else {
+        eRoster::insert(active_model).exec(db).await?;
+    }
+
+    let linked = eRosterIdea::find()
+        .filter(roster_idea::Column::Person.eq(into))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|x| x.idea)
+        .collect::<Vec<_>>();
+    for link in eRosterIdea::find()
+        .filter(roster_idea::Column::Person.eq(from))
+        .all(db)
+        .await?
+    {
+        if !linked.contains(&link.idea) {
+            eRosterIdea::insert(roster_idea::ActiveModel {
+                person: ActiveValue::Set(into),
+                idea: ActiveValue::Set(link.idea),
+            })
+            .exec(db)
+            .await?;
+        }
+    }
+    // Takes the links of `from` with it
+    eRoster::delete_by_id(from).exec(db).await?;
+    Ok(())
+}
+
+/// What is known about `into` once `from` is merged into them. What `into`
+/// already has is kept, except for the notes, which are put together
+fn merged(into: Option<roster::Model>, from: roster::Model, person: i32) -> roster::Model {
+    let Some(into) = into else {
+        return roster::Model { person, ..from };
+    };
+    let notes = [into.notes.as_str(), from.notes.as_str()]
+        .into_iter()
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let contacted = into.contacted_at.is_some();
+    roster::Model {
+        person,
+        class: if into.class.is_empty() {
+            from.class
+        } else {
+            into.class
+        },
+        contacted_at: if contacted {
+            into.contacted_at
+        } else {
+            from.contacted_at
+        },
+        contacted_by: if contacted {
+            into.contacted_by
+        } else {
+            from.contacted_by
+        },
+        notes,
+    }
+}
@@ -1,5 +1,9 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
+use crate::entities::sea_orm_active_enums::Priority;
+
+use super::idea::IdeaEntry;
+
 #[derive(Debug)]
 pub struct SearchQuery {
     data: String,
@@ -66,4 +70,45 @@ impl SearchQuery {
     pub fn to_string(&self) -> &str {
         &self.data
     }
+
+    /// Whether the idea should be shown. Words starting with `#` only keep
+    /// the ideas with that tag, ones starting with `!` the ideas with that
+    /// priority and ones starting with `@` the ideas assigned to that person.
+    /// The rest has to be the start of the title
+    pub fn matches(&self, entry: &IdeaEntry) -> bool {
+        let mut title = Vec::new();
+        for word in self.data.split_whitespace() {
+            match word.strip_prefix('#') {
+                Some("") => (),
+                Some(tag) => {
+                    if !entry.tags.iter().any(|x| x.eq_ignore_ascii_case(tag)) {
+                        return false;
+                    }
+                }
+                None if word.len() > 1 && word.starts_with('@') => {
+                    if !entry
+                        .assignees
+                        .iter()
+                        .any(|x| x.eq_ignore_ascii_case(&word[1..]))
+                    {
+                        return false;
+                    }
+                }
+                None => match word.strip_prefix('!').map(Priority::from_name) {
+                    Some(Some(priority)) => {
+                        if entry.idea.get_entry().priority != priority {
+                            return false;
+                        }
+                    }
+                    Some(None) | None => title.push(word),
+                },
+            }
+        }
+        entry
+            .idea
+            .get_entry()
+            .title
+            .to_lowercase()
+            .starts_with(&title.join(" ").to_lowercase())
+    }
 }